@@ -1,4 +1,4 @@
-from . import blackboard, imitative, config
+from . import blackboard, imitative, hybrid, config
 from .experiment import Experiment
 
-__all__ = ["Experiment", "blackboard", "imitative", "config"]
+__all__ = ["Experiment", "blackboard", "imitative", "hybrid", "config"]
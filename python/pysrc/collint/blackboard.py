@@ -7,13 +7,122 @@ from .util import (
 from .config import is_python_type_checking_enabled
 from typing import Union
 
+DEFAULT_PROBLEM = "DONALD + GERALD = ROBERT"
+DEFAULT_LEADING_ZEROS = "penalize"
+DEFAULT_LEADING_ZERO_PENALTY = 100_000_000
+DEFAULT_G = 1
+DEFAULT_GS = [1]
+DEFAULT_MIGRATION_RATE = 0.0
+DEFAULT_TOPOLOGY = "fully_connected"
+DEFAULT_TOPOLOGY_K = 2
+DEFAULT_WIDTH = 10
+DEFAULT_BETA = 0.1
+DEFAULT_LINKS = 2
+DEFAULT_EDGE_LIST = ""
+DEFAULT_REPLACEMENT = "random"
+DEFAULT_READ_POLICY = "uniform"
+DEFAULT_ADVERSARY_FRACTION = 0.0
+DEFAULT_ADVERSARY_MODE = "false"
+DEFAULT_E = 0.0
+DEFAULT_MOVE_OPERATOR = "reassign"
+DEFAULT_ROTATION_SIZE = 3
+DEFAULT_RESTART_PROBABILITY = 0.01
+DEFAULT_ACCEPTANCE = "always"
+DEFAULT_TEMPERATURE = 1.0
+DEFAULT_COOLING_RATE = 0.999
+
 
 def blackboard(
-    m: int, b: int, /, *, max_c: float = 10.0, compute_phi: bool = False
+    m: int,
+    b: int,
+    /,
+    *,
+    max_c: float = 10.0,
+    compute_phi: bool = False,
+    problem: str = DEFAULT_PROBLEM,
+    base: int = 10,
+    leading_zeros: str = DEFAULT_LEADING_ZEROS,
+    leading_zero_penalty: int = DEFAULT_LEADING_ZERO_PENALTY,
+    g: int = DEFAULT_G,
+    migration_rate: float = DEFAULT_MIGRATION_RATE,
+    topology: str = DEFAULT_TOPOLOGY,
+    topology_k: int = DEFAULT_TOPOLOGY_K,
+    width: int = DEFAULT_WIDTH,
+    beta: float = DEFAULT_BETA,
+    links: int = DEFAULT_LINKS,
+    edge_list: str = DEFAULT_EDGE_LIST,
+    replacement: str = DEFAULT_REPLACEMENT,
+    read_policy: str = DEFAULT_READ_POLICY,
+    adversary_fraction: float = DEFAULT_ADVERSARY_FRACTION,
+    adversary_mode: str = DEFAULT_ADVERSARY_MODE,
+    e: float = DEFAULT_E,
+    move_operator: str = DEFAULT_MOVE_OPERATOR,
+    rotation_size: int = DEFAULT_ROTATION_SIZE,
+    restart_probability: float = DEFAULT_RESTART_PROBABILITY,
+    acceptance: str = DEFAULT_ACCEPTANCE,
+    temperature: float = DEFAULT_TEMPERATURE,
+    cooling_rate: float = DEFAULT_COOLING_RATE,
 ) -> Union[dict, None]:
     if is_python_type_checking_enabled():
-        check_type_blackboard(m, b, max_c, compute_phi)
-    return map_result_blackboard(blackboard_rs(m, b, max_c, compute_phi), compute_phi)
+        check_type_blackboard(
+            m,
+            b,
+            max_c,
+            compute_phi,
+            problem,
+            base,
+            leading_zeros,
+            leading_zero_penalty,
+            g,
+            migration_rate,
+            topology,
+            topology_k,
+            width,
+            beta,
+            links,
+            edge_list,
+            replacement,
+            read_policy,
+            adversary_fraction,
+            adversary_mode,
+            e,
+            move_operator,
+            rotation_size,
+            restart_probability,
+            acceptance,
+            temperature,
+            cooling_rate,
+        )
+    result = blackboard_rs(
+        m,
+        b,
+        max_c,
+        compute_phi,
+        problem,
+        base,
+        leading_zeros,
+        leading_zero_penalty,
+        g,
+        migration_rate,
+        topology,
+        topology_k,
+        width,
+        beta,
+        links,
+        edge_list,
+        replacement,
+        read_policy,
+        adversary_fraction,
+        adversary_mode,
+        e,
+        move_operator,
+        rotation_size,
+        restart_probability,
+        acceptance,
+        temperature,
+        cooling_rate,
+    )
+    return map_result_blackboard(result, compute_phi)
 
 
 def blackboard_parallel(
@@ -25,8 +134,91 @@ def blackboard_parallel(
     max_c: float = 10.0,
     compute_phi: bool = False,
     show_progress: bool = True,
+    problem: str = DEFAULT_PROBLEM,
+    base: int = 10,
+    leading_zeros: str = DEFAULT_LEADING_ZEROS,
+    leading_zero_penalty: int = DEFAULT_LEADING_ZERO_PENALTY,
+    gs: "list[int]" = DEFAULT_GS,
+    migration_rate: float = DEFAULT_MIGRATION_RATE,
+    topology: str = DEFAULT_TOPOLOGY,
+    topology_k: int = DEFAULT_TOPOLOGY_K,
+    width: int = DEFAULT_WIDTH,
+    beta: float = DEFAULT_BETA,
+    links: int = DEFAULT_LINKS,
+    edge_list: str = DEFAULT_EDGE_LIST,
+    replacement: str = DEFAULT_REPLACEMENT,
+    read_policy: str = DEFAULT_READ_POLICY,
+    adversary_fraction: float = DEFAULT_ADVERSARY_FRACTION,
+    adversary_mode: str = DEFAULT_ADVERSARY_MODE,
+    e: float = DEFAULT_E,
+    move_operator: str = DEFAULT_MOVE_OPERATOR,
+    rotation_size: int = DEFAULT_ROTATION_SIZE,
+    restart_probability: float = DEFAULT_RESTART_PROBABILITY,
+    acceptance: str = DEFAULT_ACCEPTANCE,
+    temperature: float = DEFAULT_TEMPERATURE,
+    cooling_rate: float = DEFAULT_COOLING_RATE,
 ) -> "list[Union[dict, None]]":
     if is_python_type_checking_enabled():
-        check_type_blackboard_parallel(ms, bs, n, max_c, compute_phi, show_progress)
-    results = blackboard_parallel_rs(ms, bs, n, max_c, compute_phi, show_progress)
+        check_type_blackboard_parallel(
+            ms,
+            bs,
+            n,
+            max_c,
+            compute_phi,
+            show_progress,
+            problem,
+            base,
+            leading_zeros,
+            leading_zero_penalty,
+            gs,
+            migration_rate,
+            topology,
+            topology_k,
+            width,
+            beta,
+            links,
+            edge_list,
+            replacement,
+            read_policy,
+            adversary_fraction,
+            adversary_mode,
+            e,
+            move_operator,
+            rotation_size,
+            restart_probability,
+            acceptance,
+            temperature,
+            cooling_rate,
+        )
+    results = blackboard_parallel_rs(
+        ms,
+        bs,
+        n,
+        max_c,
+        compute_phi,
+        show_progress,
+        problem,
+        base,
+        leading_zeros,
+        leading_zero_penalty,
+        gs,
+        migration_rate,
+        topology,
+        topology_k,
+        width,
+        beta,
+        links,
+        edge_list,
+        replacement,
+        read_policy,
+        adversary_fraction,
+        adversary_mode,
+        e,
+        move_operator,
+        rotation_size,
+        restart_probability,
+        acceptance,
+        temperature,
+        cooling_rate,
+    )
     return [map_result_blackboard(r, compute_phi) for r in results]
@@ -1,4 +1,4 @@
-from . import imitative, blackboard, config
+from . import imitative, blackboard, hybrid, config, util
 from itertools import product
 
 
@@ -7,6 +7,44 @@ class Experiment:
         self._parallel = False
         self._type_checking = True
         self._max_c = 10.0
+        self._problem = "DONALD + GERALD = ROBERT"
+        self._base = 10
+        self._leading_zeros = "penalize"
+        self._leading_zero_penalty = 100_000_000
+        self._cost_function = "absolute_difference"
+        self._intensity = "letters"
+        self._k = 1
+        self._q = 0.5
+        self._intensities = ["letters"]
+        self._ks = [1]
+        self._qs = [0.5]
+        self._target = "best"
+        self._target_k = 2
+        self._mix = "channels"
+        self._mix_p = 0.5
+        self._mix_q = 0.5
+        self._blackboard_share = 1.0
+        self._imitator_share = 1.0
+        self._loner_share = 1.0
+        self._gs = [1]
+        self._migration_rate = 0.0
+        self._replacement = "random"
+        self._read_policy = "uniform"
+        self._adversary_fraction = 0.0
+        self._adversary_mode = "false"
+        self._e = 0.0
+        self._topology = "fully_connected"
+        self._topology_k = 2
+        self._width = 10
+        self._beta = 0.1
+        self._links = 2
+        self._edge_list = ""
+        self._move_operator = "reassign"
+        self._rotation_size = 3
+        self._restart_probability = 0.01
+        self._acceptance = "always"
+        self._temperature = 1.0
+        self._cooling_rate = 0.999
 
         self._run_type = None
 
@@ -18,12 +56,61 @@ class Experiment:
         self._max_c = max_c
         return self
 
+    def problem(
+        self,
+        problem,
+        base=10,
+        leading_zeros="penalize",
+        leading_zero_penalty=100_000_000,
+    ):
+        self._problem = problem
+        self._base = base
+        self._leading_zeros = leading_zeros
+        self._leading_zero_penalty = leading_zero_penalty
+        return self
+
+    def agent(
+        self,
+        move_operator="reassign",
+        rotation_size=3,
+        restart_probability=0.01,
+        acceptance="always",
+        temperature=1.0,
+        cooling_rate=0.999,
+    ):
+        self._move_operator = move_operator
+        self._rotation_size = rotation_size
+        self._restart_probability = restart_probability
+        self._acceptance = acceptance
+        self._temperature = temperature
+        self._cooling_rate = cooling_rate
+        return self
+
     def parallel(self, show_progress=True):
         self._parallel = (True,)
         self._show_progress = show_progress
         return self
 
-    def blackboard(self, ms, bs, n, compute_phi=False):
+    def blackboard(
+        self,
+        ms,
+        bs,
+        n,
+        compute_phi=False,
+        gs=[1],
+        migration_rate=0.0,
+        topology="fully_connected",
+        topology_k=2,
+        width=10,
+        beta=0.1,
+        links=2,
+        edge_list="",
+        replacement="random",
+        read_policy="uniform",
+        adversary_fraction=0.0,
+        adversary_mode="false",
+        e=0.0,
+    ):
         if self._run_type:
             raise ValueError("Already chose a run type")
         self._run_type = "blackboard"
@@ -31,15 +118,99 @@ class Experiment:
         self._bs = bs
         self._n = n
         self._compute_phi = compute_phi
+        self._gs = gs
+        self._migration_rate = migration_rate
+        self._topology = topology
+        self._topology_k = topology_k
+        self._width = width
+        self._beta = beta
+        self._links = links
+        self._edge_list = edge_list
+        self._replacement = replacement
+        self._read_policy = read_policy
+        self._adversary_fraction = adversary_fraction
+        self._adversary_mode = adversary_mode
+        self._e = e
         return self
 
-    def imitative(self, ms, ps, n):
+    def imitative(
+        self,
+        ms,
+        ps,
+        n,
+        cost_function="absolute_difference",
+        intensities=["letters"],
+        ks=[1],
+        qs=[0.5],
+        target="best",
+        target_k=2,
+        topology="fully_connected",
+        topology_k=2,
+        width=10,
+        beta=0.1,
+        links=2,
+        edge_list="",
+    ):
         if self._run_type:
             raise ValueError("Already chose a run type")
         self._run_type = "imitative"
         self._ms = ms
         self._ps = ps
         self._n = n
+        self._cost_function = cost_function
+        self._intensities = intensities
+        self._ks = ks
+        self._qs = qs
+        self._target = target
+        self._target_k = target_k
+        self._topology = topology
+        self._topology_k = topology_k
+        self._width = width
+        self._beta = beta
+        self._links = links
+        self._edge_list = edge_list
+        return self
+
+    def hybrid(
+        self,
+        ms,
+        bs,
+        n,
+        mix="channels",
+        p=0.5,
+        q=0.5,
+        blackboard_share=1.0,
+        imitator_share=1.0,
+        loner_share=1.0,
+        cost_function="absolute_difference",
+        intensity="letters",
+        k=1,
+        intensity_q=0.5,
+        target="best",
+        target_k=2,
+        replacement="random",
+        read_policy="uniform",
+    ):
+        if self._run_type:
+            raise ValueError("Already chose a run type")
+        self._run_type = "hybrid"
+        self._ms = ms
+        self._bs = bs
+        self._n = n
+        self._mix = mix
+        self._mix_p = p
+        self._mix_q = q
+        self._blackboard_share = blackboard_share
+        self._imitator_share = imitator_share
+        self._loner_share = loner_share
+        self._cost_function = cost_function
+        self._intensity = intensity
+        self._k = k
+        self._q = intensity_q
+        self._target = target
+        self._target_k = target_k
+        self._replacement = replacement
+        self._read_policy = read_policy
         return self
 
     def _run_parallel(self):
@@ -51,6 +222,61 @@ class Experiment:
                 max_c=self._max_c,
                 compute_phi=self._compute_phi,
                 show_progress=self._show_progress,
+                problem=self._problem,
+                base=self._base,
+                leading_zeros=self._leading_zeros,
+                leading_zero_penalty=self._leading_zero_penalty,
+                gs=self._gs,
+                migration_rate=self._migration_rate,
+                topology=self._topology,
+                topology_k=self._topology_k,
+                width=self._width,
+                beta=self._beta,
+                links=self._links,
+                edge_list=self._edge_list,
+                replacement=self._replacement,
+                read_policy=self._read_policy,
+                adversary_fraction=self._adversary_fraction,
+                adversary_mode=self._adversary_mode,
+                e=self._e,
+                move_operator=self._move_operator,
+                rotation_size=self._rotation_size,
+                restart_probability=self._restart_probability,
+                acceptance=self._acceptance,
+                temperature=self._temperature,
+                cooling_rate=self._cooling_rate,
+            )
+        elif self._run_type == "hybrid":
+            return hybrid.hybrid_parallel(
+                self._ms,
+                self._bs,
+                self._n,
+                max_c=self._max_c,
+                show_progress=self._show_progress,
+                problem=self._problem,
+                base=self._base,
+                leading_zeros=self._leading_zeros,
+                leading_zero_penalty=self._leading_zero_penalty,
+                mix=self._mix,
+                p=self._mix_p,
+                q=self._mix_q,
+                blackboard_share=self._blackboard_share,
+                imitator_share=self._imitator_share,
+                loner_share=self._loner_share,
+                cost_function=self._cost_function,
+                intensity=self._intensity,
+                k=self._k,
+                intensity_q=self._q,
+                target=self._target,
+                target_k=self._target_k,
+                replacement=self._replacement,
+                read_policy=self._read_policy,
+                move_operator=self._move_operator,
+                rotation_size=self._rotation_size,
+                restart_probability=self._restart_probability,
+                acceptance=self._acceptance,
+                temperature=self._temperature,
+                cooling_rate=self._cooling_rate,
             )
         else:
             return imitative.imitative_parallel(
@@ -59,17 +285,127 @@ class Experiment:
                 self._n,
                 max_c=self._max_c,
                 show_progress=self._show_progress,
+                problem=self._problem,
+                base=self._base,
+                leading_zeros=self._leading_zeros,
+                leading_zero_penalty=self._leading_zero_penalty,
+                cost_function=self._cost_function,
+                intensities=self._intensities,
+                ks=self._ks,
+                qs=self._qs,
+                target=self._target,
+                target_k=self._target_k,
+                topology=self._topology,
+                topology_k=self._topology_k,
+                width=self._width,
+                beta=self._beta,
+                links=self._links,
+                edge_list=self._edge_list,
+                move_operator=self._move_operator,
+                rotation_size=self._rotation_size,
+                restart_probability=self._restart_probability,
+                acceptance=self._acceptance,
+                temperature=self._temperature,
+                cooling_rate=self._cooling_rate,
             )
 
     def _yield_non_parallel(self):
         if self._run_type == "blackboard":
-            for m, b, _ in product(self._ms, self._bs, range(self._n)):
+            for m, b, g, _ in product(self._ms, self._bs, self._gs, range(self._n)):
                 yield blackboard.blackboard(
-                    m, b, max_c=self._max_c, compute_phi=self._compute_phi
+                    m,
+                    b,
+                    max_c=self._max_c,
+                    compute_phi=self._compute_phi,
+                    problem=self._problem,
+                    base=self._base,
+                    leading_zeros=self._leading_zeros,
+                    leading_zero_penalty=self._leading_zero_penalty,
+                    g=g,
+                    migration_rate=self._migration_rate,
+                    topology=self._topology,
+                    topology_k=self._topology_k,
+                    width=self._width,
+                    beta=self._beta,
+                    links=self._links,
+                    edge_list=self._edge_list,
+                    replacement=self._replacement,
+                    read_policy=self._read_policy,
+                    adversary_fraction=self._adversary_fraction,
+                    adversary_mode=self._adversary_mode,
+                    e=self._e,
+                    move_operator=self._move_operator,
+                    rotation_size=self._rotation_size,
+                    restart_probability=self._restart_probability,
+                    acceptance=self._acceptance,
+                    temperature=self._temperature,
+                    cooling_rate=self._cooling_rate,
+                )
+        elif self._run_type == "hybrid":
+            for m, b, _ in product(self._ms, self._bs, range(self._n)):
+                yield hybrid.hybrid(
+                    m,
+                    b,
+                    max_c=self._max_c,
+                    problem=self._problem,
+                    base=self._base,
+                    leading_zeros=self._leading_zeros,
+                    leading_zero_penalty=self._leading_zero_penalty,
+                    mix=self._mix,
+                    p=self._mix_p,
+                    q=self._mix_q,
+                    blackboard_share=self._blackboard_share,
+                    imitator_share=self._imitator_share,
+                    loner_share=self._loner_share,
+                    cost_function=self._cost_function,
+                    intensity=self._intensity,
+                    k=self._k,
+                    intensity_q=self._q,
+                    target=self._target,
+                    target_k=self._target_k,
+                    replacement=self._replacement,
+                    read_policy=self._read_policy,
+                    move_operator=self._move_operator,
+                    rotation_size=self._rotation_size,
+                    restart_probability=self._restart_probability,
+                    acceptance=self._acceptance,
+                    temperature=self._temperature,
+                    cooling_rate=self._cooling_rate,
                 )
         else:
-            for m, p, _ in product(self._ms, self._ps, range(self._n)):
-                yield imitative.imitative(m, p, max_c=self._max_c)
+            intensities = util.expand_intensities(
+                self._intensities, self._ks, self._qs
+            )
+            for m, p, (intensity, k, q), _ in product(
+                self._ms, self._ps, intensities, range(self._n)
+            ):
+                yield imitative.imitative(
+                    m,
+                    p,
+                    max_c=self._max_c,
+                    problem=self._problem,
+                    base=self._base,
+                    leading_zeros=self._leading_zeros,
+                    leading_zero_penalty=self._leading_zero_penalty,
+                    cost_function=self._cost_function,
+                    intensity=intensity,
+                    k=k,
+                    q=q,
+                    target=self._target,
+                    target_k=self._target_k,
+                    topology=self._topology,
+                    topology_k=self._topology_k,
+                    width=self._width,
+                    beta=self._beta,
+                    links=self._links,
+                    edge_list=self._edge_list,
+                    move_operator=self._move_operator,
+                    rotation_size=self._rotation_size,
+                    restart_probability=self._restart_probability,
+                    acceptance=self._acceptance,
+                    temperature=self._temperature,
+                    cooling_rate=self._cooling_rate,
+                )
         if not self._type_checking and self._type_checking_original:
             config.enable_python_type_checking()
 
@@ -79,7 +415,7 @@ class Experiment:
         self._type_checking_original = config.is_python_type_checking_enabled()
         if self._run_type is None:
             raise ValueError(
-                "Cannot run without calling either blackboard or imitative method"
+                "Cannot run without calling the blackboard, imitative or hybrid method"
             )
         if not self._type_checking:
             config.disable_python_type_checking()
@@ -0,0 +1,222 @@
+from .collint import hybrid_rs, hybrid_parallel_rs
+from .util import (
+    check_type_hybrid,
+    check_type_hybrid_parallel,
+    map_result_hybrid,
+)
+from .config import is_python_type_checking_enabled
+from typing import Union
+
+DEFAULT_PROBLEM = "DONALD + GERALD = ROBERT"
+DEFAULT_LEADING_ZEROS = "penalize"
+DEFAULT_LEADING_ZERO_PENALTY = 100_000_000
+DEFAULT_MIX = "channels"
+DEFAULT_P = 0.5
+DEFAULT_Q = 0.5
+DEFAULT_SHARE = 1.0
+DEFAULT_COST_FUNCTION = "absolute_difference"
+DEFAULT_INTENSITY = "letters"
+DEFAULT_K = 1
+DEFAULT_INTENSITY_Q = 0.5
+DEFAULT_TARGET = "best"
+DEFAULT_TARGET_K = 2
+DEFAULT_REPLACEMENT = "random"
+DEFAULT_READ_POLICY = "uniform"
+DEFAULT_MOVE_OPERATOR = "reassign"
+DEFAULT_ROTATION_SIZE = 3
+DEFAULT_RESTART_PROBABILITY = 0.01
+DEFAULT_ACCEPTANCE = "always"
+DEFAULT_TEMPERATURE = 1.0
+DEFAULT_COOLING_RATE = 0.999
+
+
+def hybrid(
+    m: int,
+    b: int,
+    /,
+    *,
+    max_c: float = 10.0,
+    problem: str = DEFAULT_PROBLEM,
+    base: int = 10,
+    leading_zeros: str = DEFAULT_LEADING_ZEROS,
+    leading_zero_penalty: int = DEFAULT_LEADING_ZERO_PENALTY,
+    mix: str = DEFAULT_MIX,
+    p: float = DEFAULT_P,
+    q: float = DEFAULT_Q,
+    blackboard_share: float = DEFAULT_SHARE,
+    imitator_share: float = DEFAULT_SHARE,
+    loner_share: float = DEFAULT_SHARE,
+    cost_function: str = DEFAULT_COST_FUNCTION,
+    intensity: str = DEFAULT_INTENSITY,
+    k: int = DEFAULT_K,
+    intensity_q: float = DEFAULT_INTENSITY_Q,
+    target: str = DEFAULT_TARGET,
+    target_k: int = DEFAULT_TARGET_K,
+    replacement: str = DEFAULT_REPLACEMENT,
+    read_policy: str = DEFAULT_READ_POLICY,
+    move_operator: str = DEFAULT_MOVE_OPERATOR,
+    rotation_size: int = DEFAULT_ROTATION_SIZE,
+    restart_probability: float = DEFAULT_RESTART_PROBABILITY,
+    acceptance: str = DEFAULT_ACCEPTANCE,
+    temperature: float = DEFAULT_TEMPERATURE,
+    cooling_rate: float = DEFAULT_COOLING_RATE,
+) -> Union[dict, None]:
+    if is_python_type_checking_enabled():
+        check_type_hybrid(
+            m,
+            b,
+            max_c,
+            problem,
+            base,
+            leading_zeros,
+            leading_zero_penalty,
+            mix,
+            p,
+            q,
+            blackboard_share,
+            imitator_share,
+            loner_share,
+            cost_function,
+            intensity,
+            k,
+            intensity_q,
+            target,
+            target_k,
+            replacement,
+            read_policy,
+            move_operator,
+            rotation_size,
+            restart_probability,
+            acceptance,
+            temperature,
+            cooling_rate,
+        )
+    result = hybrid_rs(
+        m,
+        b,
+        max_c,
+        problem,
+        base,
+        leading_zeros,
+        leading_zero_penalty,
+        mix,
+        p,
+        q,
+        blackboard_share,
+        imitator_share,
+        loner_share,
+        cost_function,
+        intensity,
+        k,
+        intensity_q,
+        target,
+        target_k,
+        replacement,
+        read_policy,
+        move_operator,
+        rotation_size,
+        restart_probability,
+        acceptance,
+        temperature,
+        cooling_rate,
+    )
+    return map_result_hybrid(result)
+
+
+def hybrid_parallel(
+    ms: "list[int]",
+    bs: "list[int]",
+    n: int,
+    /,
+    *,
+    max_c: float = 10.0,
+    show_progress: bool = True,
+    problem: str = DEFAULT_PROBLEM,
+    base: int = 10,
+    leading_zeros: str = DEFAULT_LEADING_ZEROS,
+    leading_zero_penalty: int = DEFAULT_LEADING_ZERO_PENALTY,
+    mix: str = DEFAULT_MIX,
+    p: float = DEFAULT_P,
+    q: float = DEFAULT_Q,
+    blackboard_share: float = DEFAULT_SHARE,
+    imitator_share: float = DEFAULT_SHARE,
+    loner_share: float = DEFAULT_SHARE,
+    cost_function: str = DEFAULT_COST_FUNCTION,
+    intensity: str = DEFAULT_INTENSITY,
+    k: int = DEFAULT_K,
+    intensity_q: float = DEFAULT_INTENSITY_Q,
+    target: str = DEFAULT_TARGET,
+    target_k: int = DEFAULT_TARGET_K,
+    replacement: str = DEFAULT_REPLACEMENT,
+    read_policy: str = DEFAULT_READ_POLICY,
+    move_operator: str = DEFAULT_MOVE_OPERATOR,
+    rotation_size: int = DEFAULT_ROTATION_SIZE,
+    restart_probability: float = DEFAULT_RESTART_PROBABILITY,
+    acceptance: str = DEFAULT_ACCEPTANCE,
+    temperature: float = DEFAULT_TEMPERATURE,
+    cooling_rate: float = DEFAULT_COOLING_RATE,
+) -> "list[Union[dict, None]]":
+    if is_python_type_checking_enabled():
+        check_type_hybrid_parallel(
+            ms,
+            bs,
+            n,
+            max_c,
+            show_progress,
+            problem,
+            base,
+            leading_zeros,
+            leading_zero_penalty,
+            mix,
+            p,
+            q,
+            blackboard_share,
+            imitator_share,
+            loner_share,
+            cost_function,
+            intensity,
+            k,
+            intensity_q,
+            target,
+            target_k,
+            replacement,
+            read_policy,
+            move_operator,
+            rotation_size,
+            restart_probability,
+            acceptance,
+            temperature,
+            cooling_rate,
+        )
+    results = hybrid_parallel_rs(
+        ms,
+        bs,
+        n,
+        max_c,
+        show_progress,
+        problem,
+        base,
+        leading_zeros,
+        leading_zero_penalty,
+        mix,
+        p,
+        q,
+        blackboard_share,
+        imitator_share,
+        loner_share,
+        cost_function,
+        intensity,
+        k,
+        intensity_q,
+        target,
+        target_k,
+        replacement,
+        read_policy,
+        move_operator,
+        rotation_size,
+        restart_probability,
+        acceptance,
+        temperature,
+        cooling_rate,
+    )
+    return [map_result_hybrid(r) for r in results]
@@ -2,16 +2,123 @@ from .collint import imitative_rs, imitative_parallel_rs
 from .util import (
     check_type_imitative,
     check_type_imitative_parallel,
+    expand_intensities,
     map_result_imitative,
 )
 from .config import is_python_type_checking_enabled
 from typing import Union
 
+DEFAULT_PROBLEM = "DONALD + GERALD = ROBERT"
+DEFAULT_LEADING_ZEROS = "penalize"
+DEFAULT_LEADING_ZERO_PENALTY = 100_000_000
+DEFAULT_COST_FUNCTION = "absolute_difference"
+DEFAULT_INTENSITY = "letters"
+DEFAULT_K = 1
+DEFAULT_Q = 0.5
+DEFAULT_INTENSITIES = [DEFAULT_INTENSITY]
+DEFAULT_KS = [DEFAULT_K]
+DEFAULT_QS = [DEFAULT_Q]
+DEFAULT_TARGET = "best"
+DEFAULT_TARGET_K = 2
+DEFAULT_TOPOLOGY = "fully_connected"
+DEFAULT_TOPOLOGY_K = 2
+DEFAULT_WIDTH = 10
+DEFAULT_BETA = 0.1
+DEFAULT_LINKS = 2
+DEFAULT_EDGE_LIST = ""
+DEFAULT_MOVE_OPERATOR = "reassign"
+DEFAULT_ROTATION_SIZE = 3
+DEFAULT_RESTART_PROBABILITY = 0.01
+DEFAULT_ACCEPTANCE = "always"
+DEFAULT_TEMPERATURE = 1.0
+DEFAULT_COOLING_RATE = 0.999
 
-def imitative(m: int, p: float, /, *, max_c: float = 10.0) -> Union[dict, None]:
+
+def imitative(
+    m: int,
+    p: float,
+    /,
+    *,
+    max_c: float = 10.0,
+    problem: str = DEFAULT_PROBLEM,
+    base: int = 10,
+    leading_zeros: str = DEFAULT_LEADING_ZEROS,
+    leading_zero_penalty: int = DEFAULT_LEADING_ZERO_PENALTY,
+    cost_function: str = DEFAULT_COST_FUNCTION,
+    intensity: str = DEFAULT_INTENSITY,
+    k: int = DEFAULT_K,
+    q: float = DEFAULT_Q,
+    target: str = DEFAULT_TARGET,
+    target_k: int = DEFAULT_TARGET_K,
+    topology: str = DEFAULT_TOPOLOGY,
+    topology_k: int = DEFAULT_TOPOLOGY_K,
+    width: int = DEFAULT_WIDTH,
+    beta: float = DEFAULT_BETA,
+    links: int = DEFAULT_LINKS,
+    edge_list: str = DEFAULT_EDGE_LIST,
+    move_operator: str = DEFAULT_MOVE_OPERATOR,
+    rotation_size: int = DEFAULT_ROTATION_SIZE,
+    restart_probability: float = DEFAULT_RESTART_PROBABILITY,
+    acceptance: str = DEFAULT_ACCEPTANCE,
+    temperature: float = DEFAULT_TEMPERATURE,
+    cooling_rate: float = DEFAULT_COOLING_RATE,
+) -> Union[dict, None]:
     if is_python_type_checking_enabled():
-        check_type_imitative(m, p, max_c)
-    return map_result_imitative(imitative_rs(m, p, max_c))
+        check_type_imitative(
+            m,
+            p,
+            max_c,
+            problem,
+            base,
+            leading_zeros,
+            leading_zero_penalty,
+            cost_function,
+            intensity,
+            k,
+            q,
+            target,
+            target_k,
+            topology,
+            topology_k,
+            width,
+            beta,
+            links,
+            edge_list,
+            move_operator,
+            rotation_size,
+            restart_probability,
+            acceptance,
+            temperature,
+            cooling_rate,
+        )
+    result = imitative_rs(
+        m,
+        p,
+        max_c,
+        problem,
+        base,
+        leading_zeros,
+        leading_zero_penalty,
+        cost_function,
+        intensity,
+        k,
+        q,
+        target,
+        target_k,
+        topology,
+        topology_k,
+        width,
+        beta,
+        links,
+        edge_list,
+        move_operator,
+        rotation_size,
+        restart_probability,
+        acceptance,
+        temperature,
+        cooling_rate,
+    )
+    return map_result_imitative(result)
 
 
 def imitative_parallel(
@@ -22,8 +129,84 @@ def imitative_parallel(
     *,
     max_c: float = 10.0,
     show_progress: bool = True,
+    problem: str = DEFAULT_PROBLEM,
+    base: int = 10,
+    leading_zeros: str = DEFAULT_LEADING_ZEROS,
+    leading_zero_penalty: int = DEFAULT_LEADING_ZERO_PENALTY,
+    cost_function: str = DEFAULT_COST_FUNCTION,
+    intensities: "list[str]" = DEFAULT_INTENSITIES,
+    ks: "list[int]" = DEFAULT_KS,
+    qs: "list[float]" = DEFAULT_QS,
+    target: str = DEFAULT_TARGET,
+    target_k: int = DEFAULT_TARGET_K,
+    topology: str = DEFAULT_TOPOLOGY,
+    topology_k: int = DEFAULT_TOPOLOGY_K,
+    width: int = DEFAULT_WIDTH,
+    beta: float = DEFAULT_BETA,
+    links: int = DEFAULT_LINKS,
+    edge_list: str = DEFAULT_EDGE_LIST,
+    move_operator: str = DEFAULT_MOVE_OPERATOR,
+    rotation_size: int = DEFAULT_ROTATION_SIZE,
+    restart_probability: float = DEFAULT_RESTART_PROBABILITY,
+    acceptance: str = DEFAULT_ACCEPTANCE,
+    temperature: float = DEFAULT_TEMPERATURE,
+    cooling_rate: float = DEFAULT_COOLING_RATE,
 ) -> "list[Union[dict, None]]":
     if is_python_type_checking_enabled():
-        check_type_imitative_parallel(ms, ps, n, max_c, show_progress)
-    results = imitative_parallel_rs(ms, ps, n, max_c, show_progress)
+        check_type_imitative_parallel(
+            ms,
+            ps,
+            n,
+            max_c,
+            show_progress,
+            problem,
+            base,
+            leading_zeros,
+            leading_zero_penalty,
+            cost_function,
+            intensities,
+            ks,
+            qs,
+            target,
+            target_k,
+            topology,
+            topology_k,
+            width,
+            beta,
+            links,
+            edge_list,
+            move_operator,
+            rotation_size,
+            restart_probability,
+            acceptance,
+            temperature,
+            cooling_rate,
+        )
+    results = imitative_parallel_rs(
+        ms,
+        ps,
+        n,
+        max_c,
+        show_progress,
+        problem,
+        base,
+        leading_zeros,
+        leading_zero_penalty,
+        cost_function,
+        expand_intensities(intensities, ks, qs),
+        target,
+        target_k,
+        topology,
+        topology_k,
+        width,
+        beta,
+        links,
+        edge_list,
+        move_operator,
+        rotation_size,
+        restart_probability,
+        acceptance,
+        temperature,
+        cooling_rate,
+    )
     return [map_result_imitative(r) for r in results]
@@ -1,4 +1,272 @@
-def check_type_blackboard(m: int, b: int, max_c: float, compute_phi: bool):
+LEADING_ZEROS = ("allow", "penalize", "reject")
+
+
+def check_type_problem(problem: str, base: int, leading_zeros: str):
+    if type(problem) is not str:
+        raise ValueError("problem must be a str.")
+    if type(base) is not int:
+        raise ValueError("base must be an int.")
+    if base < 2:
+        raise ValueError("base must be at least 2.")
+    if type(leading_zeros) is not str:
+        raise ValueError("leading_zeros must be a str.")
+    if leading_zeros not in LEADING_ZEROS:
+        raise ValueError(f"leading_zeros must be one of {', '.join(LEADING_ZEROS)}.")
+
+
+def check_type_leading_zero_penalty(leading_zero_penalty: int):
+    if type(leading_zero_penalty) is not int:
+        raise ValueError("leading_zero_penalty must be an int.")
+    if leading_zero_penalty < 0:
+        raise ValueError("leading_zero_penalty must be at least 0.")
+
+
+COST_FUNCTIONS = ("absolute_difference", "column_mismatch", "digit_hamming")
+
+
+def check_type_cost_function(cost_function: str):
+    if type(cost_function) is not str:
+        raise ValueError("cost_function must be a str.")
+    if cost_function not in COST_FUNCTIONS:
+        raise ValueError(f"cost_function must be one of {', '.join(COST_FUNCTIONS)}.")
+
+
+MOVE_OPERATORS = ("reassign", "swap", "rotate", "column_scramble", "restart")
+ACCEPTANCES = (
+    "always",
+    "greedy",
+    "metropolis",
+    "exponential_annealing",
+    "logarithmic_annealing",
+)
+
+
+def check_type_agent(
+    move_operator: str,
+    rotation_size: int,
+    restart_probability: float,
+    acceptance: str,
+    temperature: float,
+    cooling_rate: float,
+):
+    if type(move_operator) is not str:
+        raise ValueError("move_operator must be a str.")
+    if move_operator not in MOVE_OPERATORS:
+        raise ValueError(f"move_operator must be one of {', '.join(MOVE_OPERATORS)}.")
+    if type(rotation_size) is not int:
+        raise ValueError("rotation_size must be an int.")
+    if rotation_size < 2:
+        raise ValueError("rotation_size must be at least 2.")
+    if type(restart_probability) is not float:
+        raise ValueError("restart_probability must be a float.")
+    if restart_probability < 0 or restart_probability > 1.0:
+        raise ValueError("restart_probability must be between 0.0 and 1.0.")
+    if type(acceptance) is not str:
+        raise ValueError("acceptance must be a str.")
+    if acceptance not in ACCEPTANCES:
+        raise ValueError(f"acceptance must be one of {', '.join(ACCEPTANCES)}.")
+    if type(temperature) is not float:
+        raise ValueError("temperature must be a float.")
+    if temperature <= 0.0:
+        raise ValueError("temperature must be greater than 0.")
+    if type(cooling_rate) is not float:
+        raise ValueError("cooling_rate must be a float.")
+    if cooling_rate <= 0.0 or cooling_rate >= 1.0:
+        raise ValueError("cooling_rate must be between 0.0 and 1.0.")
+
+
+INTENSITIES = ("letters", "column", "independent")
+
+
+def check_type_intensity(intensity: str, k: int, q: float, q_name: str = "q"):
+    if type(intensity) is not str:
+        raise ValueError("intensity must be a str.")
+    if intensity not in INTENSITIES:
+        raise ValueError(f"intensity must be one of {', '.join(INTENSITIES)}.")
+    if type(k) is not int:
+        raise ValueError("k must be an int.")
+    if k <= 0:
+        raise ValueError("k must be greater than 0.")
+    if type(q) is not float:
+        raise ValueError(f"{q_name} must be a float.")
+    if q <= 0 or q > 1.0:
+        raise ValueError(f"{q_name} must be greater than 0.0 and at most 1.0.")
+
+
+# every intensity with each of its parameters, letters takes every k, independent
+# every q and column has none
+def expand_intensities(
+    intensities: "list[str]", ks: "list[int]", qs: "list[float]"
+) -> "list[tuple[str, int, float]]":
+    expanded = []
+    for intensity in intensities:
+        if intensity == "letters":
+            expanded += [(intensity, k, qs[0]) for k in ks]
+        elif intensity == "independent":
+            expanded += [(intensity, ks[0], q) for q in qs]
+        else:
+            expanded.append((intensity, ks[0], qs[0]))
+    return expanded
+
+
+def check_type_intensities(
+    intensities: "list[str]", ks: "list[int]", qs: "list[float]"
+):
+    for values, name in ((intensities, "intensities"), (ks, "ks"), (qs, "qs")):
+        if type(values) is not list:
+            raise ValueError(f"{name} must be a list.")
+        if len(values) == 0:
+            raise ValueError(f"Must be at least one value in {name}.")
+    for k in ks:
+        for q in qs:
+            for intensity in intensities:
+                check_type_intensity(intensity, k, q)
+
+
+TARGETS = ("best", "random_better", "tournament", "top_k", "proportional")
+
+
+def check_type_target(target: str, target_k: int):
+    if type(target) is not str:
+        raise ValueError("target must be a str.")
+    if target not in TARGETS:
+        raise ValueError(f"target must be one of {', '.join(TARGETS)}.")
+    if type(target_k) is not int:
+        raise ValueError("target_k must be an int.")
+    if target_k <= 0:
+        raise ValueError("target_k must be greater than 0.")
+
+
+TOPOLOGIES = (
+    "fully_connected",
+    "ring",
+    "lattice",
+    "small_world",
+    "scale_free",
+    "edge_list",
+)
+
+
+def check_type_topology(
+    topology: str,
+    topology_k: int,
+    width: int,
+    beta: float,
+    links: int,
+    edge_list: str,
+):
+    if type(topology) is not str:
+        raise ValueError("topology must be a str.")
+    if topology not in TOPOLOGIES:
+        raise ValueError(f"topology must be one of {', '.join(TOPOLOGIES)}.")
+    if type(topology_k) is not int:
+        raise ValueError("topology_k must be an int.")
+    if topology_k <= 0:
+        raise ValueError("topology_k must be greater than 0.")
+    if type(width) is not int:
+        raise ValueError("width must be an int.")
+    if width <= 0:
+        raise ValueError("width must be greater than 0.")
+    if type(beta) is not float:
+        raise ValueError("beta must be a float.")
+    if beta < 0 or beta > 1.0:
+        raise ValueError("beta must be between 0.0 and 1.0.")
+    if type(links) is not int:
+        raise ValueError("links must be an int.")
+    if links <= 0:
+        raise ValueError("links must be greater than 0.")
+    if type(edge_list) is not str:
+        raise ValueError("edge_list must be a str.")
+    if topology == "edge_list" and not edge_list:
+        raise ValueError("edge_list must name a file for the edge_list topology.")
+
+
+def check_type_migration(
+    migration_rate: float,
+    topology: str,
+    topology_k: int,
+    width: int,
+    beta: float,
+    links: int,
+    edge_list: str,
+):
+    if type(migration_rate) is not float:
+        raise ValueError("migration_rate must be a float.")
+    if migration_rate < 0 or migration_rate > 1.0:
+        raise ValueError("migration_rate must be between 0.0 and 1.0.")
+    check_type_topology(topology, topology_k, width, beta, links, edge_list)
+
+
+REPLACEMENTS = (
+    "random",
+    "fifo",
+    "least_recently_read",
+    "least_popular",
+    "quality_weighted",
+)
+
+
+READ_POLICIES = ("uniform", "votes", "reads", "holders")
+
+
+def check_type_replacement(replacement: str, read_policy: str):
+    if type(replacement) is not str:
+        raise ValueError("replacement must be a str.")
+    if replacement not in REPLACEMENTS:
+        raise ValueError(f"replacement must be one of {', '.join(REPLACEMENTS)}.")
+    if type(read_policy) is not str:
+        raise ValueError("read_policy must be a str.")
+    if read_policy not in READ_POLICIES:
+        raise ValueError(f"read_policy must be one of {', '.join(READ_POLICIES)}.")
+
+
+ADVERSARY_MODES = ("false", "random", "noisy")
+
+
+def check_type_adversaries(adversary_fraction: float, adversary_mode: str, e: float):
+    if type(adversary_fraction) is not float:
+        raise ValueError("adversary_fraction must be a float.")
+    if adversary_fraction < 0 or adversary_fraction > 1.0:
+        raise ValueError("adversary_fraction must be between 0.0 and 1.0.")
+    if type(adversary_mode) is not str:
+        raise ValueError("adversary_mode must be a str.")
+    if adversary_mode not in ADVERSARY_MODES:
+        raise ValueError(f"adversary_mode must be one of {', '.join(ADVERSARY_MODES)}.")
+    if type(e) is not float:
+        raise ValueError("e must be a float.")
+    if e < 0 or e > 1.0:
+        raise ValueError("e must be between 0.0 and 1.0.")
+
+
+def check_type_blackboard(
+    m: int,
+    b: int,
+    max_c: float,
+    compute_phi: bool,
+    problem: str,
+    base: int,
+    leading_zeros: str,
+    leading_zero_penalty: int,
+    g: int,
+    migration_rate: float,
+    topology: str,
+    topology_k: int,
+    width: int,
+    beta: float,
+    links: int,
+    edge_list: str,
+    replacement: str,
+    read_policy: str,
+    adversary_fraction: float,
+    adversary_mode: str,
+    e: float,
+    move_operator: str,
+    rotation_size: int,
+    restart_probability: float,
+    acceptance: str,
+    temperature: float,
+    cooling_rate: float,
+):
     if type(m) is not int:
         raise ValueError("m must be an int.")
     if m <= 0:
@@ -13,6 +281,25 @@ def check_type_blackboard(m: int, b: int, max_c: float, compute_phi: bool):
         raise ValueError("max_c must be greater than 0.")
     if type(compute_phi) is not bool:
         raise ValueError("compute_phi must be a bool.")
+    if type(g) is not int:
+        raise ValueError("g must be an int.")
+    if g <= 0:
+        raise ValueError("g must be greater than 0.")
+    check_type_problem(problem, base, leading_zeros)
+    check_type_leading_zero_penalty(leading_zero_penalty)
+    check_type_migration(
+        migration_rate, topology, topology_k, width, beta, links, edge_list
+    )
+    check_type_replacement(replacement, read_policy)
+    check_type_adversaries(adversary_fraction, adversary_mode, e)
+    check_type_agent(
+        move_operator,
+        rotation_size,
+        restart_probability,
+        acceptance,
+        temperature,
+        cooling_rate,
+    )
 
 
 def check_type_blackboard_parallel(
@@ -22,6 +309,29 @@ def check_type_blackboard_parallel(
     max_c: float,
     compute_phi: bool,
     show_progress: bool,
+    problem: str,
+    base: int,
+    leading_zeros: str,
+    leading_zero_penalty: int,
+    gs: "list[int]",
+    migration_rate: float,
+    topology: str,
+    topology_k: int,
+    width: int,
+    beta: float,
+    links: int,
+    edge_list: str,
+    replacement: str,
+    read_policy: str,
+    adversary_fraction: float,
+    adversary_mode: str,
+    e: float,
+    move_operator: str,
+    rotation_size: int,
+    restart_probability: float,
+    acceptance: str,
+    temperature: float,
+    cooling_rate: float,
 ):
     if type(ms) is not list:
         raise ValueError("M must be a list.")
@@ -43,6 +353,16 @@ def check_type_blackboard_parallel(
         if b <= 0:
             raise ValueError("values in B must be greater than 0.")
 
+    if type(gs) is not list:
+        raise ValueError("G must be a list.")
+    if len(gs) == 0:
+        raise ValueError("Must be at least one value in G.")
+    for g in gs:
+        if type(g) is not int:
+            raise ValueError("values in G must be an int.")
+        if g <= 0:
+            raise ValueError("values in G must be greater than 0.")
+
     if type(n) is not int:
         raise ValueError("n must be an int.")
     if n < 1:
@@ -56,9 +376,50 @@ def check_type_blackboard_parallel(
         raise ValueError("compute_phi must be a bool.")
     if type(show_progress) is not bool:
         raise ValueError("show_progress must be a bool.")
+    check_type_problem(problem, base, leading_zeros)
+    check_type_leading_zero_penalty(leading_zero_penalty)
+    check_type_migration(
+        migration_rate, topology, topology_k, width, beta, links, edge_list
+    )
+    check_type_replacement(replacement, read_policy)
+    check_type_adversaries(adversary_fraction, adversary_mode, e)
+    check_type_agent(
+        move_operator,
+        rotation_size,
+        restart_probability,
+        acceptance,
+        temperature,
+        cooling_rate,
+    )
 
 
-def check_type_imitative(m: int, p: float, max_c: float):
+def check_type_imitative(
+    m: int,
+    p: float,
+    max_c: float,
+    problem: str,
+    base: int,
+    leading_zeros: str,
+    leading_zero_penalty: int,
+    cost_function: str,
+    intensity: str,
+    k: int,
+    q: float,
+    target: str,
+    target_k: int,
+    topology: str,
+    topology_k: int,
+    width: int,
+    beta: float,
+    links: int,
+    edge_list: str,
+    move_operator: str,
+    rotation_size: int,
+    restart_probability: float,
+    acceptance: str,
+    temperature: float,
+    cooling_rate: float,
+):
     if type(m) is not int:
         raise ValueError("m must be an int.")
     if m <= 0:
@@ -71,10 +432,50 @@ def check_type_imitative(m: int, p: float, max_c: float):
         raise ValueError("max_c must be a float.")
     if max_c < 0.0:
         raise ValueError("max_c must be greater than 0.")
+    check_type_problem(problem, base, leading_zeros)
+    check_type_leading_zero_penalty(leading_zero_penalty)
+    check_type_cost_function(cost_function)
+    check_type_intensity(intensity, k, q)
+    check_type_target(target, target_k)
+    check_type_topology(topology, topology_k, width, beta, links, edge_list)
+    check_type_agent(
+        move_operator,
+        rotation_size,
+        restart_probability,
+        acceptance,
+        temperature,
+        cooling_rate,
+    )
 
 
 def check_type_imitative_parallel(
-    ms: "list[int]", ps: "list[float]", n: int, max_c: float, show_progress: bool
+    ms: "list[int]",
+    ps: "list[float]",
+    n: int,
+    max_c: float,
+    show_progress: bool,
+    problem: str,
+    base: int,
+    leading_zeros: str,
+    leading_zero_penalty: int,
+    cost_function: str,
+    intensities: "list[str]",
+    ks: "list[int]",
+    qs: "list[float]",
+    target: str,
+    target_k: int,
+    topology: str,
+    topology_k: int,
+    width: int,
+    beta: float,
+    links: int,
+    edge_list: str,
+    move_operator: str,
+    rotation_size: int,
+    restart_probability: float,
+    acceptance: str,
+    temperature: float,
+    cooling_rate: float,
 ):
     if type(ms) is not list:
         raise ValueError("M must be a list.")
@@ -107,14 +508,222 @@ def check_type_imitative_parallel(
         raise ValueError("max_c must be greater than 0.")
     if type(show_progress) is not bool:
         raise ValueError("show_progress must be a bool.")
+    check_type_problem(problem, base, leading_zeros)
+    check_type_leading_zero_penalty(leading_zero_penalty)
+    check_type_cost_function(cost_function)
+    check_type_intensities(intensities, ks, qs)
+    check_type_target(target, target_k)
+    check_type_topology(topology, topology_k, width, beta, links, edge_list)
+    check_type_agent(
+        move_operator,
+        rotation_size,
+        restart_probability,
+        acceptance,
+        temperature,
+        cooling_rate,
+    )
+
+
+MIXES = ("types", "channels")
+
+
+def check_type_mix(
+    mix: str,
+    p: float,
+    q: float,
+    blackboard_share: float,
+    imitator_share: float,
+    loner_share: float,
+):
+    if type(mix) is not str:
+        raise ValueError("mix must be a str.")
+    if mix not in MIXES:
+        raise ValueError(f"mix must be one of {', '.join(MIXES)}.")
+    if type(p) is not float:
+        raise ValueError("p must be a float.")
+    if p < 0 or p > 1.0:
+        raise ValueError("p must be between 0.0 and 1.0.")
+    if type(q) is not float:
+        raise ValueError("q must be a float.")
+    if q < 0 or q > 1.0:
+        raise ValueError("q must be between 0.0 and 1.0.")
+    shares = (blackboard_share, imitator_share, loner_share)
+    if any(type(share) is not float for share in shares):
+        raise ValueError("shares must be floats.")
+    if any(share < 0 for share in shares):
+        raise ValueError("shares must be at least 0.0.")
+    if sum(shares) <= 0:
+        raise ValueError("shares must not all be 0.0.")
+
+
+def check_type_hybrid(
+    m: int,
+    b: int,
+    max_c: float,
+    problem: str,
+    base: int,
+    leading_zeros: str,
+    leading_zero_penalty: int,
+    mix: str,
+    p: float,
+    q: float,
+    blackboard_share: float,
+    imitator_share: float,
+    loner_share: float,
+    cost_function: str,
+    intensity: str,
+    k: int,
+    intensity_q: float,
+    target: str,
+    target_k: int,
+    replacement: str,
+    read_policy: str,
+    move_operator: str,
+    rotation_size: int,
+    restart_probability: float,
+    acceptance: str,
+    temperature: float,
+    cooling_rate: float,
+):
+    if type(m) is not int:
+        raise ValueError("m must be an int.")
+    if m <= 0:
+        raise ValueError("m must be greater than 0.")
+    if type(b) is not int:
+        raise ValueError("b must be an int.")
+    if b <= 0:
+        raise ValueError("b must be greater than 0.")
+    if type(max_c) is not float:
+        raise ValueError("max_c must be a float.")
+    if max_c < 0.0:
+        raise ValueError("max_c must be greater than 0.")
+    check_type_problem(problem, base, leading_zeros)
+    check_type_leading_zero_penalty(leading_zero_penalty)
+    check_type_mix(mix, p, q, blackboard_share, imitator_share, loner_share)
+    check_type_cost_function(cost_function)
+    check_type_intensity(intensity, k, intensity_q, "intensity_q")
+    check_type_target(target, target_k)
+    check_type_replacement(replacement, read_policy)
+    check_type_agent(
+        move_operator,
+        rotation_size,
+        restart_probability,
+        acceptance,
+        temperature,
+        cooling_rate,
+    )
+
+
+def check_type_hybrid_parallel(
+    ms: "list[int]",
+    bs: "list[int]",
+    n: int,
+    max_c: float,
+    show_progress: bool,
+    problem: str,
+    base: int,
+    leading_zeros: str,
+    leading_zero_penalty: int,
+    mix: str,
+    p: float,
+    q: float,
+    blackboard_share: float,
+    imitator_share: float,
+    loner_share: float,
+    cost_function: str,
+    intensity: str,
+    k: int,
+    intensity_q: float,
+    target: str,
+    target_k: int,
+    replacement: str,
+    read_policy: str,
+    move_operator: str,
+    rotation_size: int,
+    restart_probability: float,
+    acceptance: str,
+    temperature: float,
+    cooling_rate: float,
+):
+    if type(ms) is not list:
+        raise ValueError("M must be a list.")
+    if len(ms) == 0:
+        raise ValueError("Must be at least one value in M.")
+    if type(bs) is not list:
+        raise ValueError("B must be a list.")
+    if len(bs) == 0:
+        raise ValueError("Must be at least one value in B.")
+
+    for m in ms:
+        if type(m) is not int:
+            raise ValueError("values in M must be an int.")
+        if m <= 0:
+            raise ValueError("values in M must be greater than 0.")
+    for b in bs:
+        if type(b) is not int:
+            raise ValueError("values in B must be an int.")
+        if b <= 0:
+            raise ValueError("values in B must be greater than 0.")
+
+    if type(n) is not int:
+        raise ValueError("n must be an int.")
+    if n < 1:
+        raise ValueError("n must be greater than 0.")
+
+    if type(max_c) is not float:
+        raise ValueError("max_c must be a float.")
+    if max_c < 0.0:
+        raise ValueError("max_c must be greater than 0.")
+    if type(show_progress) is not bool:
+        raise ValueError("show_progress must be a bool.")
+    check_type_problem(problem, base, leading_zeros)
+    check_type_leading_zero_penalty(leading_zero_penalty)
+    check_type_mix(mix, p, q, blackboard_share, imitator_share, loner_share)
+    check_type_cost_function(cost_function)
+    check_type_intensity(intensity, k, intensity_q, "intensity_q")
+    check_type_target(target, target_k)
+    check_type_replacement(replacement, read_policy)
+    check_type_agent(
+        move_operator,
+        rotation_size,
+        restart_probability,
+        acceptance,
+        temperature,
+        cooling_rate,
+    )
 
 
 def map_result_blackboard(result, compute_phi):
     if not result:
         return None
 
-    m, b, t_star, phi, c = result
-    ret = {"m": m, "b": b, "t_star": t_star, "c": c}
+    (
+        m,
+        b,
+        t_star,
+        phi,
+        c,
+        g,
+        migrations,
+        replacement,
+        read_policy,
+        adversaries,
+        bad_hints,
+        forged_hints,
+    ) = result
+    ret = {
+        "m": m,
+        "b": b,
+        "t_star": t_star,
+        "c": c,
+        "g": g,
+        "migrations": migrations,
+        "replacement": replacement,
+        "read_policy": read_policy,
+        "adversaries": adversaries,
+        "bad_hints": bad_hints,
+        "forged_hints": forged_hints,
+    }
     if compute_phi:
         ret["phi"] = phi
     return ret
@@ -124,6 +733,52 @@ def map_result_imitative(result):
     if not result:
         return None
 
-    m, p, t_star, c = result
-    ret = {"m": m, "p": p, "t_star": t_star, "c": c}
+    m, p, t_star, c, cost_function, intensity, target, topology = result
+    ret = {
+        "m": m,
+        "p": p,
+        "t_star": t_star,
+        "c": c,
+        "cost_function": cost_function,
+        "intensity": intensity,
+        "target": target,
+        "topology": topology,
+    }
+    return ret
+
+
+def map_result_hybrid(result):
+    if not result:
+        return None
+
+    (
+        m,
+        b,
+        t_star,
+        c,
+        mix,
+        users,
+        imitators,
+        loners,
+        p,
+        q,
+        (cost_function, intensity, target, replacement, read_policy),
+    ) = result
+    ret = {
+        "m": m,
+        "b": b,
+        "t_star": t_star,
+        "c": c,
+        "mix": mix,
+        "users": users,
+        "imitators": imitators,
+        "loners": loners,
+        "p": p,
+        "q": q,
+        "cost_function": cost_function,
+        "intensity": intensity,
+        "target": target,
+        "replacement": replacement,
+        "read_policy": read_policy,
+    }
     return ret
@@ -1,5 +1,207 @@
 use collint_lib;
-use pyo3::prelude::*;
+use pyo3::{exceptions::PyValueError, prelude::*};
+
+fn build_problem(problem: &str, base: u32, leading_zeros: &str) -> PyResult<collint_lib::Problem> {
+    let to_py = |e: collint_lib::ProblemError| PyValueError::new_err(e.to_string());
+    let leading_zeros = leading_zeros
+        .parse()
+        .map_err(|e: collint_lib::UnknownLeadingZeros| PyValueError::new_err(e.to_string()))?;
+    let problem = collint_lib::Problem::parse(problem, base)
+        .map_err(to_py)?
+        .with_leading_zeros(leading_zeros);
+    // the models expect a problem with a solution, as the config loader checks
+    if !problem.is_solvable() {
+        return Err(PyValueError::new_err("error: problem has no solution"));
+    }
+    Ok(problem)
+}
+
+fn parse_cost_function(cost_function: &str) -> PyResult<collint_lib::CostKind> {
+    cost_function
+        .parse()
+        .map_err(|e: collint_lib::UnknownCostFunction| PyValueError::new_err(e.to_string()))
+}
+
+fn build_intensity(intensity: &str, k: usize, q: f64) -> PyResult<collint_lib::ImitationIntensity> {
+    let intensity = match intensity {
+        "letters" => collint_lib::ImitationIntensity::Letters { k },
+        "column" => collint_lib::ImitationIntensity::Column,
+        "independent" => collint_lib::ImitationIntensity::Independent { q },
+        _ => {
+            return Err(PyValueError::new_err(format!(
+                "error: unknown imitation intensity '{}', expected letters, column or independent",
+                intensity
+            )))
+        }
+    };
+    intensity.validate().map_err(PyValueError::new_err)?;
+    Ok(intensity)
+}
+
+fn build_target(target: &str, k: usize) -> PyResult<collint_lib::ImitationTarget> {
+    let target = match target {
+        "best" => collint_lib::ImitationTarget::Best,
+        "random_better" => collint_lib::ImitationTarget::RandomBetter,
+        "tournament" => collint_lib::ImitationTarget::Tournament { k },
+        "top_k" => collint_lib::ImitationTarget::TopK { k },
+        "proportional" => collint_lib::ImitationTarget::Proportional,
+        _ => {
+            return Err(PyValueError::new_err(format!(
+                "error: unknown imitation target '{}', expected best, random_better, tournament, top_k or proportional",
+                target
+            )))
+        }
+    };
+    target.validate().map_err(PyValueError::new_err)?;
+    Ok(target)
+}
+
+fn build_mix(
+    mix: &str,
+    p: f64,
+    q: f64,
+    blackboard_share: f64,
+    imitator_share: f64,
+    loner_share: f64,
+) -> PyResult<collint_lib::Mix> {
+    let mix = match mix {
+        "types" => collint_lib::Mix::Types {
+            blackboard: blackboard_share,
+            imitator: imitator_share,
+            loner: loner_share,
+            p,
+        },
+        "channels" => collint_lib::Mix::Channels { p, q },
+        _ => {
+            return Err(PyValueError::new_err(format!(
+                "error: unknown mix '{}', expected types or channels",
+                mix
+            )))
+        }
+    };
+    mix.validate().map_err(PyValueError::new_err)?;
+    Ok(mix)
+}
+
+fn build_topology(
+    topology: &str,
+    k: usize,
+    width: usize,
+    beta: f64,
+    links: usize,
+    edge_list: &str,
+    sizes: &[u32],
+) -> PyResult<collint_lib::Topology> {
+    let mut topology = match topology {
+        "fully_connected" => collint_lib::Topology::FullyConnected,
+        "ring" => collint_lib::Topology::Ring { k },
+        "lattice" => collint_lib::Topology::Lattice { width },
+        "small_world" => collint_lib::Topology::SmallWorld { k, beta },
+        "scale_free" => collint_lib::Topology::ScaleFree { links },
+        "edge_list" => collint_lib::Topology::EdgeList {
+            path: edge_list.to_string(),
+            edges: Vec::new(),
+        },
+        _ => {
+            return Err(PyValueError::new_err(format!(
+                "error: unknown topology '{}', expected fully_connected, ring, lattice, small_world, scale_free or edge_list",
+                topology
+            )))
+        }
+    };
+    topology.load().map_err(PyValueError::new_err)?;
+    for m in sizes {
+        topology
+            .check_size(*m as usize)
+            .map_err(PyValueError::new_err)?;
+    }
+    Ok(topology)
+}
+
+fn build_migration(
+    rate: f64,
+    topology: &str,
+    k: usize,
+    width: usize,
+    beta: f64,
+    links: usize,
+    edge_list: &str,
+    sizes: &[u32],
+) -> PyResult<collint_lib::Migration> {
+    let mut migration = collint_lib::Migration {
+        rate,
+        topology: build_topology(topology, k, width, beta, links, edge_list, sizes)?,
+    };
+    migration.load().map_err(PyValueError::new_err)?;
+    Ok(migration)
+}
+
+fn build_options(
+    move_operator: &str,
+    rotation_size: usize,
+    restart_probability: f64,
+    acceptance: &str,
+    temperature: f64,
+    cooling_rate: f64,
+) -> PyResult<collint_lib::AgentOptions> {
+    let move_operator = move_operator
+        .parse()
+        .map_err(|e: collint_lib::UnknownMoveOperator| PyValueError::new_err(e.to_string()))?;
+    let acceptance = acceptance
+        .parse()
+        .map_err(|e: collint_lib::UnknownAcceptance| PyValueError::new_err(e.to_string()))?;
+    let options = collint_lib::AgentOptions {
+        move_operator,
+        rotation_size,
+        restart_probability,
+        acceptance,
+        temperature,
+        cooling_rate,
+    };
+    options.validate().map_err(PyValueError::new_err)?;
+    Ok(options)
+}
+
+fn build_adversaries(fraction: f64, mode: &str, e: f64) -> PyResult<collint_lib::Adversaries> {
+    let mode = mode
+        .parse()
+        .map_err(|e: collint_lib::UnknownAdversaryMode| PyValueError::new_err(e.to_string()))?;
+    let adversaries = collint_lib::Adversaries { fraction, mode, e };
+    adversaries.validate().map_err(PyValueError::new_err)?;
+    Ok(adversaries)
+}
+
+type BlackboardRow = (
+    u32,
+    u32,
+    f64,
+    f64,
+    f64,
+    u32,
+    u32,
+    String,
+    String,
+    u32,
+    u32,
+    u32,
+);
+
+fn blackboard_row(r: &collint_lib::BlackboardResult) -> BlackboardRow {
+    (
+        r.m,
+        r.b,
+        r.t_star,
+        r.phi.unwrap_or(f64::NAN),
+        r.c,
+        r.g,
+        r.migrations,
+        r.replacement.to_string(),
+        r.read_policy.to_string(),
+        r.adversaries,
+        r.bad_hints,
+        r.forged_hints,
+    )
+}
 
 #[pyfunction]
 fn blackboard_rs(
@@ -7,17 +209,72 @@ fn blackboard_rs(
     b: u32,
     max_c: f64,
     compute_phi: bool,
-) -> Option<(u32, u32, f64, f64, f64)> {
-    collint_lib::blackboard(m, b, max_c, compute_phi).map_or(
-        None,
-        |collint_lib::BlackboardResult {
-             m,
-             b,
-             t_star,
-             phi,
-             c,
-         }| Some((m, b, t_star, phi, c)),
+    problem: &str,
+    base: u32,
+    leading_zeros: &str,
+    leading_zero_penalty: u64,
+    g: u32,
+    migration_rate: f64,
+    topology: &str,
+    topology_k: usize,
+    width: usize,
+    beta: f64,
+    links: usize,
+    edge_list: &str,
+    replacement: &str,
+    read_policy: &str,
+    adversary_fraction: f64,
+    adversary_mode: &str,
+    e: f64,
+    move_operator: &str,
+    rotation_size: usize,
+    restart_probability: f64,
+    acceptance: &str,
+    temperature: f64,
+    cooling_rate: f64,
+) -> PyResult<Option<BlackboardRow>> {
+    let problem = build_problem(problem, base, leading_zeros)?
+        .with_leading_zero_penalty(leading_zero_penalty as u128);
+    let migration = build_migration(
+        migration_rate,
+        topology,
+        topology_k,
+        width,
+        beta,
+        links,
+        edge_list,
+        &[g],
+    )?;
+    let replacement = replacement
+        .parse()
+        .map_err(|e: collint_lib::UnknownReplacement| PyValueError::new_err(e.to_string()))?;
+    let read_policy = read_policy
+        .parse()
+        .map_err(|e: collint_lib::UnknownReadPolicy| PyValueError::new_err(e.to_string()))?;
+    let adversaries = build_adversaries(adversary_fraction, adversary_mode, e)?;
+    let options = build_options(
+        move_operator,
+        rotation_size,
+        restart_probability,
+        acceptance,
+        temperature,
+        cooling_rate,
+    )?;
+    Ok(collint_lib::blackboard(
+        &problem,
+        m,
+        b,
+        g,
+        &migration,
+        replacement,
+        read_policy,
+        adversaries,
+        max_c,
+        compute_phi,
+        options,
     )
+    .as_ref()
+    .map(blackboard_row))
 }
 
 #[pyfunction]
@@ -28,28 +285,147 @@ fn blackboard_parallel_rs(
     max_c: f64,
     compute_phi: bool,
     show_progress: bool,
-) -> Vec<Option<(u32, u32, f64, f64, f64)>> {
-    collint_lib::blackboard_parallel(ms, bs, n, max_c, compute_phi, show_progress)
-        .iter()
-        .map(|r| match *r {
-            Some(collint_lib::BlackboardResult {
-                m,
-                b,
-                t_star,
-                phi,
-                c,
-            }) => Some((m, b, t_star, phi, c)),
-            None => None,
-        })
-        .collect()
+    problem: &str,
+    base: u32,
+    leading_zeros: &str,
+    leading_zero_penalty: u64,
+    gs: Vec<u32>,
+    migration_rate: f64,
+    topology: &str,
+    topology_k: usize,
+    width: usize,
+    beta: f64,
+    links: usize,
+    edge_list: &str,
+    replacement: &str,
+    read_policy: &str,
+    adversary_fraction: f64,
+    adversary_mode: &str,
+    e: f64,
+    move_operator: &str,
+    rotation_size: usize,
+    restart_probability: f64,
+    acceptance: &str,
+    temperature: f64,
+    cooling_rate: f64,
+) -> PyResult<Vec<Option<BlackboardRow>>> {
+    let problem = build_problem(problem, base, leading_zeros)?
+        .with_leading_zero_penalty(leading_zero_penalty as u128);
+    let migration = build_migration(
+        migration_rate,
+        topology,
+        topology_k,
+        width,
+        beta,
+        links,
+        edge_list,
+        &gs,
+    )?;
+    let replacement = replacement
+        .parse()
+        .map_err(|e: collint_lib::UnknownReplacement| PyValueError::new_err(e.to_string()))?;
+    let read_policy = read_policy
+        .parse()
+        .map_err(|e: collint_lib::UnknownReadPolicy| PyValueError::new_err(e.to_string()))?;
+    let adversaries = build_adversaries(adversary_fraction, adversary_mode, e)?;
+    let options = build_options(
+        move_operator,
+        rotation_size,
+        restart_probability,
+        acceptance,
+        temperature,
+        cooling_rate,
+    )?;
+    Ok(collint_lib::blackboard_parallel(
+        &problem,
+        ms,
+        bs,
+        gs,
+        n,
+        &migration,
+        replacement,
+        read_policy,
+        adversaries,
+        max_c,
+        compute_phi,
+        options,
+        show_progress,
+    )
+    .iter()
+    .map(|r| r.as_ref().map(blackboard_row))
+    .collect())
+}
+
+type ImitativeRow = (u32, f64, f64, f64, String, String, String, String);
+
+fn imitative_row(r: &collint_lib::ImitativeResult) -> ImitativeRow {
+    (
+        r.m,
+        r.p,
+        r.t_star,
+        r.c,
+        r.settings.cost_function.to_string(),
+        r.settings.intensity.to_string(),
+        r.settings.target.to_string(),
+        r.topology.clone(),
+    )
 }
 
 #[pyfunction]
-fn imitative_rs(m: u32, p: f64, max_c: f64) -> Option<(u32, f64, f64, f64)> {
-    collint_lib::imitative(m, p, max_c)
-        .map_or(None, |collint_lib::ImitativeResult { m, p, t_star, c }| {
-            Some((m, p, t_star, c))
-        })
+fn imitative_rs(
+    m: u32,
+    p: f64,
+    max_c: f64,
+    problem: &str,
+    base: u32,
+    leading_zeros: &str,
+    leading_zero_penalty: u64,
+    cost_function: &str,
+    intensity: &str,
+    k: usize,
+    q: f64,
+    target: &str,
+    target_k: usize,
+    topology: &str,
+    topology_k: usize,
+    width: usize,
+    beta: f64,
+    links: usize,
+    edge_list: &str,
+    move_operator: &str,
+    rotation_size: usize,
+    restart_probability: f64,
+    acceptance: &str,
+    temperature: f64,
+    cooling_rate: f64,
+) -> PyResult<Option<ImitativeRow>> {
+    let problem = build_problem(problem, base, leading_zeros)?
+        .with_leading_zero_penalty(leading_zero_penalty as u128);
+    let cost_function = parse_cost_function(cost_function)?;
+    let intensity = build_intensity(intensity, k, q)?;
+    let target = build_target(target, target_k)?;
+    let topology = build_topology(topology, topology_k, width, beta, links, edge_list, &[m])?;
+    let options = build_options(
+        move_operator,
+        rotation_size,
+        restart_probability,
+        acceptance,
+        temperature,
+        cooling_rate,
+    )?;
+    Ok(collint_lib::imitative(
+        &problem,
+        m,
+        p,
+        max_c,
+        cost_function,
+        intensity,
+        target,
+        &topology,
+        options,
+    )
+    .as_ref()
+    .map(imitative_row))
 }
 
 #[pyfunction]
@@ -59,14 +435,237 @@ fn imitative_parallel_rs(
     n: u32,
     max_c: f64,
     show_progress: bool,
-) -> Vec<Option<(u32, f64, f64, f64)>> {
-    collint_lib::imitative_parallel(ms, ps, n, max_c, show_progress)
+    problem: &str,
+    base: u32,
+    leading_zeros: &str,
+    leading_zero_penalty: u64,
+    cost_function: &str,
+    intensities: Vec<(String, usize, f64)>,
+    target: &str,
+    target_k: usize,
+    topology: &str,
+    topology_k: usize,
+    width: usize,
+    beta: f64,
+    links: usize,
+    edge_list: &str,
+    move_operator: &str,
+    rotation_size: usize,
+    restart_probability: f64,
+    acceptance: &str,
+    temperature: f64,
+    cooling_rate: f64,
+) -> PyResult<Vec<Option<ImitativeRow>>> {
+    let problem = build_problem(problem, base, leading_zeros)?
+        .with_leading_zero_penalty(leading_zero_penalty as u128);
+    let cost_function = parse_cost_function(cost_function)?;
+    let intensities = intensities
         .iter()
-        .map(|r| match *r {
-            Some(collint_lib::ImitativeResult { m, p, t_star, c }) => Some((m, p, t_star, c)),
-            None => None,
-        })
-        .collect()
+        .map(|(intensity, k, q)| build_intensity(intensity, *k, *q))
+        .collect::<PyResult<Vec<_>>>()?;
+    let target = build_target(target, target_k)?;
+    let topology = build_topology(topology, topology_k, width, beta, links, edge_list, &ms)?;
+    let options = build_options(
+        move_operator,
+        rotation_size,
+        restart_probability,
+        acceptance,
+        temperature,
+        cooling_rate,
+    )?;
+    Ok(collint_lib::imitative_parallel(
+        &problem,
+        ms,
+        ps,
+        n,
+        max_c,
+        cost_function,
+        intensities,
+        target,
+        &topology,
+        options,
+        show_progress,
+    )
+    .iter()
+    .map(|r| r.as_ref().map(imitative_row))
+    .collect())
+}
+
+// the settings of the run are nested, pyo3 converts tuples of at most 12 values
+type HybridRow = (
+    u32,
+    u32,
+    f64,
+    f64,
+    String,
+    u32,
+    u32,
+    u32,
+    f64,
+    f64,
+    (String, String, String, String, String),
+);
+
+fn hybrid_row(r: &collint_lib::HybridResult) -> HybridRow {
+    let (p, q) = r.mix.probabilities();
+    (
+        r.m,
+        r.b,
+        r.t_star,
+        r.c,
+        r.mix.name().to_string(),
+        r.users,
+        r.imitators,
+        r.loners,
+        p,
+        q,
+        (
+            r.settings.cost_function.to_string(),
+            r.settings.intensity.to_string(),
+            r.settings.target.to_string(),
+            r.replacement.to_string(),
+            r.read_policy.to_string(),
+        ),
+    )
+}
+
+#[pyfunction]
+fn hybrid_rs(
+    m: u32,
+    b: u32,
+    max_c: f64,
+    problem: &str,
+    base: u32,
+    leading_zeros: &str,
+    leading_zero_penalty: u64,
+    mix: &str,
+    p: f64,
+    q: f64,
+    blackboard_share: f64,
+    imitator_share: f64,
+    loner_share: f64,
+    cost_function: &str,
+    intensity: &str,
+    k: usize,
+    intensity_q: f64,
+    target: &str,
+    target_k: usize,
+    replacement: &str,
+    read_policy: &str,
+    move_operator: &str,
+    rotation_size: usize,
+    restart_probability: f64,
+    acceptance: &str,
+    temperature: f64,
+    cooling_rate: f64,
+) -> PyResult<Option<HybridRow>> {
+    let problem = build_problem(problem, base, leading_zeros)?
+        .with_leading_zero_penalty(leading_zero_penalty as u128);
+    let mix = build_mix(mix, p, q, blackboard_share, imitator_share, loner_share)?;
+    let cost_function = parse_cost_function(cost_function)?;
+    let intensity = build_intensity(intensity, k, intensity_q)?;
+    let target = build_target(target, target_k)?;
+    let replacement = replacement
+        .parse()
+        .map_err(|e: collint_lib::UnknownReplacement| PyValueError::new_err(e.to_string()))?;
+    let read_policy = read_policy
+        .parse()
+        .map_err(|e: collint_lib::UnknownReadPolicy| PyValueError::new_err(e.to_string()))?;
+    let options = build_options(
+        move_operator,
+        rotation_size,
+        restart_probability,
+        acceptance,
+        temperature,
+        cooling_rate,
+    )?;
+    Ok(collint_lib::hybrid(
+        &problem,
+        m,
+        b,
+        &mix,
+        max_c,
+        cost_function,
+        intensity,
+        target,
+        replacement,
+        read_policy,
+        options,
+    )
+    .as_ref()
+    .map(hybrid_row))
+}
+
+#[pyfunction]
+fn hybrid_parallel_rs(
+    ms: Vec<u32>,
+    bs: Vec<u32>,
+    n: u32,
+    max_c: f64,
+    show_progress: bool,
+    problem: &str,
+    base: u32,
+    leading_zeros: &str,
+    leading_zero_penalty: u64,
+    mix: &str,
+    p: f64,
+    q: f64,
+    blackboard_share: f64,
+    imitator_share: f64,
+    loner_share: f64,
+    cost_function: &str,
+    intensity: &str,
+    k: usize,
+    intensity_q: f64,
+    target: &str,
+    target_k: usize,
+    replacement: &str,
+    read_policy: &str,
+    move_operator: &str,
+    rotation_size: usize,
+    restart_probability: f64,
+    acceptance: &str,
+    temperature: f64,
+    cooling_rate: f64,
+) -> PyResult<Vec<Option<HybridRow>>> {
+    let problem = build_problem(problem, base, leading_zeros)?
+        .with_leading_zero_penalty(leading_zero_penalty as u128);
+    let mix = build_mix(mix, p, q, blackboard_share, imitator_share, loner_share)?;
+    let cost_function = parse_cost_function(cost_function)?;
+    let intensity = build_intensity(intensity, k, intensity_q)?;
+    let target = build_target(target, target_k)?;
+    let replacement = replacement
+        .parse()
+        .map_err(|e: collint_lib::UnknownReplacement| PyValueError::new_err(e.to_string()))?;
+    let read_policy = read_policy
+        .parse()
+        .map_err(|e: collint_lib::UnknownReadPolicy| PyValueError::new_err(e.to_string()))?;
+    let options = build_options(
+        move_operator,
+        rotation_size,
+        restart_probability,
+        acceptance,
+        temperature,
+        cooling_rate,
+    )?;
+    Ok(collint_lib::hybrid_parallel(
+        &problem,
+        ms,
+        bs,
+        n,
+        &mix,
+        max_c,
+        cost_function,
+        intensity,
+        target,
+        replacement,
+        read_policy,
+        options,
+        show_progress,
+    )
+    .iter()
+    .map(|r| r.as_ref().map(hybrid_row))
+    .collect())
 }
 
 #[pymodule]
@@ -75,6 +674,7 @@ fn collint(_py: Python, m: &PyModule) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(blackboard_parallel_rs, m)?)?;
     m.add_function(wrap_pyfunction!(imitative_rs, m)?)?;
     m.add_function(wrap_pyfunction!(imitative_parallel_rs, m)?)?;
+    m.add_function(wrap_pyfunction!(hybrid_rs, m)?)?;
+    m.add_function(wrap_pyfunction!(hybrid_parallel_rs, m)?)?;
     Ok(())
 }
-
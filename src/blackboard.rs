@@ -1,110 +1,274 @@
-use crate::common::{computational_cost, Agent, Hint, Problem};
+use crate::common::{computational_cost, random_agent, sweep, Problem, ProblemConfigData};
+use crate::network::Migration;
+use crate::options::{Adversaries, AgentOptions, ReadPolicy, Replacement};
+use crate::strategy::{Blackboard, Strategy};
 use config::Config;
-use indicatif::ProgressBar;
-use itertools::{iproduct, Itertools};
-use rand::seq::SliceRandom;
-use rayon::prelude::*;
+use itertools::iproduct;
+use rand::{seq::SliceRandom, Rng};
 use serde::Deserialize;
-use std::{cmp::Ordering, env, fs::File, io::prelude::*, io::BufWriter, ops::Range, sync::Mutex};
+use std::{
+    cell::Cell, cmp::Ordering, env, fmt, fs::File, io::prelude::*, io::BufWriter, ops::Range,
+};
 
 #[derive(Debug)]
 pub struct BlackboardResult {
     pub m: u32,
     pub b: u32,
     pub t_star: f64,
-    pub phi: f64,
+    // mean hint accuracy of the agents, only computed if asked for
+    pub phi: Option<f64>,
     pub c: f64,
+    // number of islands and how many hints migrated between their boards
+    pub g: u32,
+    pub migrations: u32,
+    // policies the boards replaced their hints and the agents read them by
+    pub replacement: Replacement,
+    pub read_policy: ReadPolicy,
+    // adversaries among the agents, hints that reached a board by posting or migration and are not
+    // part of any solution, and posted hints the adversaries forged
+    pub adversaries: u32,
+    pub bad_hints: u32,
+    pub forged_hints: u32,
 }
 
-impl ToString for BlackboardResult {
-    fn to_string(&self) -> String {
-        format!("{},{},{},{}", self.m, self.b, self.t_star, self.c)
+impl fmt::Display for BlackboardResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{},{},{},{}", self.m, self.b, self.t_star, self.c)?;
+        // phi has no column unless it was computed
+        if let Some(phi) = self.phi {
+            write!(f, ",{}", phi)?;
+        }
+        writeln!(
+            f,
+            ",{},{},{},{},{},{},{}",
+            self.g,
+            self.migrations,
+            self.replacement,
+            self.read_policy,
+            self.adversaries,
+            self.bad_hints,
+            self.forged_hints
+        )
     }
 }
 
-fn calculate_phi(agents: &Vec<Agent>) -> f64 {
+fn calculate_phi<S: Strategy<Blackboard>>(agents: &[S]) -> f64 {
     let mut phi: f64 = 0.0;
     for a in agents.iter() {
-        phi += (a.correct_hints as f64) / (a.total_hints as f64);
+        phi += a.hint_accuracy();
     }
     phi / (agents.len() as f64)
 }
 
-pub fn blackboard(m: u32, b: u32, max_c: f64, compute_phi: bool) -> Option<BlackboardResult> {
-    let problem = Problem::new("DONALD", "GERALD", "ROBERT");
+// with the migration rate board i sends a random hint to a board linked to it, links holds the
+// boards i is linked to if it is not linked to all of them. Returns true if the hint was new
+fn migrate(
+    problem: &Problem,
+    boards: &mut [Blackboard],
+    i: usize,
+    rate: f64,
+    links: Option<&[usize]>,
+) -> bool {
+    let mut rng = rand::thread_rng();
+    if boards.len() < 2 || !rng.gen_bool(rate) {
+        return false;
+    }
+    let to = match links {
+        Some(links) => match links.choose(&mut rng) {
+            Some(j) => *j,
+            None => return false,
+        },
+        None => (i + rng.gen_range(1..boards.len())) % boards.len(),
+    };
+    if boards[i].hints().is_empty() {
+        return false;
+    }
+    let k = rng.gen_range(0..boards[i].hints().len());
+    let (hint, poster) = (boards[i].hints()[k].clone(), boards[i].records()[k].poster);
+    boards[to].receive(hint, poster, problem)
+}
 
+#[allow(clippy::too_many_arguments)]
+pub fn blackboard(
+    problem: &Problem,
+    m: u32,
+    b: u32,
+    g: u32,
+    migration: &Migration,
+    replacement: Replacement,
+    read_policy: ReadPolicy,
+    adversaries: Adversaries,
+    max_c: f64,
+    compute_phi: bool,
+    options: AgentOptions,
+) -> Option<BlackboardResult> {
+    // the first agents spawned are the adversaries
+    let count = adversaries.count(m);
+    let spawned = Cell::new(0);
+    blackboard_with(
+        problem,
+        m,
+        b,
+        g,
+        migration,
+        replacement,
+        read_policy,
+        max_c,
+        compute_phi,
+        || {
+            let agent = random_agent(problem, options, compute_phi);
+            spawned.set(spawned.get() + 1);
+            if spawned.get() <= count {
+                agent.with_adversary(adversaries)
+            } else {
+                agent
+            }
+        },
+    )
+    .map(|r| BlackboardResult {
+        adversaries: count,
+        ..r
+    })
+}
+
+// blackboard model with the agents spawn creates, agent i shares the board of island i % g. Only
+// spawn knows which agents are adversaries, so the result counts none. Panics without islands,
+// the config and the python bindings reject them
+#[allow(clippy::too_many_arguments)]
+pub fn blackboard_with<S: Strategy<Blackboard>>(
+    problem: &Problem,
+    m: u32,
+    b: u32,
+    g: u32,
+    migration: &Migration,
+    replacement: Replacement,
+    read_policy: ReadPolicy,
+    max_c: f64,
+    compute_phi: bool,
+    spawn: impl Fn() -> S,
+) -> Option<BlackboardResult> {
+    assert!(g > 0, "blackboard model needs at least one island");
     let delta = 1.0 / (m as f64);
-    let mut blackboard: Vec<Hint> = Vec::new();
-    blackboard.reserve(b as usize);
-    let mut agents = Vec::new();
-    for _ in 0..m {
-        agents.push(Agent::new(&problem, compute_phi));
-    }
+    let mut boards: Vec<Blackboard> = (0..g)
+        .map(|_| Blackboard::new(b as usize, replacement, read_policy))
+        .collect();
+    let mut agents: Vec<S> = (0..m).map(|_| spawn()).collect();
+    let links = migration.topology.neighbours(boards.len());
+    let mut migrations = 0;
 
     let mut t = 1.0;
 
-    for a in agents.iter_mut() {
-        a.assign_random();
-        a.find_hints();
-        a.pick_and_replace(&mut blackboard);
+    for (i, a) in agents.iter_mut().enumerate() {
+        let board = &mut boards[i % g as usize];
+        board.begin_turn(i);
+        a.contribute(board);
     }
 
-    while computational_cost(m, t) < max_c {
-        let a = agents.choose_mut(&mut rand::thread_rng()).unwrap();
-        a.make_move(&mut blackboard);
-        a.find_hints();
+    while computational_cost(problem, m, t) < max_c {
+        let i = rand::thread_rng().gen_range(0..agents.len());
+        let island = i % g as usize;
+        let a = &mut agents[i];
+        a.observe(&boards[island]);
+        a.act();
         t += delta;
-        a.pick_and_replace(&mut blackboard);
+        boards[island].begin_turn(i);
+        a.contribute(&mut boards[island]);
         if a.is_solved() {
             return Some(BlackboardResult {
                 m,
                 b,
                 t_star: t,
-                phi: if compute_phi {
-                    calculate_phi(&agents)
-                } else {
-                    f64::NAN
-                },
-                c: computational_cost(m, t),
+                phi: compute_phi.then(|| calculate_phi(&agents)),
+                c: computational_cost(problem, m, t),
+                g,
+                migrations,
+                replacement,
+                read_policy,
+                adversaries: 0,
+                bad_hints: boards.iter().map(|b| b.bad_posts()).sum(),
+                forged_hints: boards.iter().map(|b| b.forged_posts()).sum(),
             });
         }
+        let island_links = links.as_ref().map(|l| l[island].as_slice());
+        if migrate(problem, &mut boards, island, migration.rate, island_links) {
+            migrations += 1;
+        }
     }
     None
 }
 
-#[allow(dead_code)]
+#[allow(dead_code, clippy::too_many_arguments)]
 pub fn blackboard_parallel(
+    problem: &Problem,
     ms: Vec<u32>,
     bs: Vec<u32>,
+    gs: Vec<u32>,
     n: u32,
+    migration: &Migration,
+    replacement: Replacement,
+    read_policy: ReadPolicy,
+    adversaries: Adversaries,
     max_c: f64,
     compute_phi: bool,
+    options: AgentOptions,
     show_progress: bool,
 ) -> Vec<Option<BlackboardResult>> {
-    let results: Mutex<Vec<Option<BlackboardResult>>> = Mutex::new(vec![]);
-    let jobs_len = ms.len() * bs.len() * n as usize;
-
-    if show_progress {
-        let pb = Mutex::new(ProgressBar::new(jobs_len as u64));
-
-        iproduct!(ms, bs, 0..n)
-            .collect_vec()
-            .into_par_iter()
-            .for_each(|(m, b, _)| {
-                let r = blackboard(m, b, max_c, compute_phi);
-                pb.lock().unwrap().inc(1);
-                results.lock().unwrap().push(r);
-            });
-    } else {
-        iproduct!(ms, bs, 0..n)
-            .collect_vec()
-            .into_par_iter()
-            .for_each(|(m, b, _)| {
-                let r = blackboard(m, b, max_c, compute_phi);
-                results.lock().unwrap().push(r);
-            });
-    }
-    results.into_inner().expect("Error unwrapping results")
+    sweep(
+        iproduct!(ms, bs, gs).collect(),
+        n,
+        show_progress,
+        |(m, b, g)| {
+            blackboard(
+                problem,
+                m,
+                b,
+                g,
+                migration,
+                replacement,
+                read_policy,
+                adversaries,
+                max_c,
+                compute_phi,
+                options,
+            )
+        },
+    )
+}
+
+#[allow(dead_code, clippy::too_many_arguments)]
+pub fn blackboard_parallel_with<S: Strategy<Blackboard>>(
+    problem: &Problem,
+    ms: Vec<u32>,
+    bs: Vec<u32>,
+    gs: Vec<u32>,
+    n: u32,
+    migration: &Migration,
+    replacement: Replacement,
+    read_policy: ReadPolicy,
+    max_c: f64,
+    compute_phi: bool,
+    show_progress: bool,
+    spawn: impl Fn() -> S + Sync,
+) -> Vec<Option<BlackboardResult>> {
+    sweep(
+        iproduct!(ms, bs, gs).collect(),
+        n,
+        show_progress,
+        |(m, b, g)| {
+            blackboard_with(
+                problem,
+                m,
+                b,
+                g,
+                migration,
+                replacement,
+                read_policy,
+                max_c,
+                compute_phi,
+                &spawn,
+            )
+        },
+    )
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -113,15 +277,29 @@ pub struct BlackboardConfigData {
     pub blackboard_sizes: Option<Vec<u32>>,
     pub group_range: Option<Range<u32>>,
     pub blackboard_range: Option<Range<u32>>,
+    pub island_counts: Option<Vec<u32>>,
+    pub island_range: Option<Range<u32>>,
     pub n_repeat: u32,
     pub output: String,
     pub use_threads: bool,
     pub flush_frequency: u32,
     pub compute_phi: bool,
+    #[serde(default)]
+    pub problem: ProblemConfigData,
+    #[serde(default)]
+    pub migration: Migration,
+    #[serde(default)]
+    pub replacement: Replacement,
+    #[serde(default)]
+    pub read_policy: ReadPolicy,
+    #[serde(default)]
+    pub adversaries: Adversaries,
+    #[serde(default)]
+    pub agent: AgentOptions,
 }
 
 impl BlackboardConfigData {
-    // populates group_sizes and blackboard_sizes with Some
+    // populates group_sizes, blackboard_sizes and island_counts with Some
     fn standardize(&mut self) {
         let group_sizes = match &self.group_sizes {
             Some(v) => v.clone(),
@@ -137,14 +315,23 @@ impl BlackboardConfigData {
                 None => unreachable!(),
             },
         };
+        let island_counts = match &self.island_counts {
+            Some(v) => v.clone(),
+            None => match &self.island_range {
+                Some(r) => (r.start..=r.end).collect(),
+                None => vec![1],
+            },
+        };
         self.group_sizes = Some(group_sizes);
         self.blackboard_sizes = Some(blackboard_sizes);
+        self.island_counts = Some(island_counts);
     }
 }
 
 impl BlackboardConfigData {
     pub fn log(&self) {
         println!("info: starting batch",);
+        self.problem.log();
         println!("    - output:          {}", self.output);
         println!("    - use_threads:     {}", self.use_threads);
         println!("    - n_repeat:        {}", self.n_repeat);
@@ -156,8 +343,17 @@ impl BlackboardConfigData {
             "    - blackboard:      {:?}",
             self.blackboard_sizes.clone().unwrap()
         );
+        println!(
+            "    - islands:         {:?}",
+            self.island_counts.clone().unwrap()
+        );
+        println!("    - migration:       {}", self.migration);
+        println!("    - replacement:     {}", self.replacement);
+        println!("    - read_policy:     {}", self.read_policy);
+        println!("    - adversaries:     {}", self.adversaries);
         println!("    - flush_frequency: {}", self.flush_frequency);
         println!("    - compute_phi:     {}", self.compute_phi);
+        self.agent.log();
     }
 }
 
@@ -196,8 +392,27 @@ pub fn blackboard_get_args() -> Result<BlackboardConfigData, Box<dyn std::error:
             "n_repeat must be > 0".to_string(),
         ))?;
     }
+    cfg.agent.validate().map_err(config::ConfigError::Message)?;
+    cfg.migration.load().map_err(config::ConfigError::Message)?;
+    cfg.adversaries
+        .validate()
+        .map_err(config::ConfigError::Message)?;
+    cfg.problem
+        .validate()
+        .map_err(config::ConfigError::Message)?;
 
     cfg.standardize();
+    if cfg.island_counts.as_ref().unwrap().contains(&0) {
+        Err(config::ConfigError::Message(
+            "island counts must be > 0".to_string(),
+        ))?;
+    }
+    for g in cfg.island_counts.as_ref().unwrap() {
+        cfg.migration
+            .topology
+            .check_size(*g as usize)
+            .map_err(config::ConfigError::Message)?;
+    }
     Ok(cfg)
 }
 
@@ -205,27 +420,20 @@ pub struct BlackboardResultsWriter {
     file: BufWriter<File>,
     counter: u32,
     flush_frequency: u32,
-    compute_phi: bool,
 }
 
 impl BlackboardResultsWriter {
-    pub fn new(filename: String, flush_frequency: u32, compute_phi: bool) -> Self {
+    pub fn new(filename: String, flush_frequency: u32) -> Self {
         Self {
             file: BufWriter::new(File::create(filename).unwrap()),
             counter: 0,
             flush_frequency,
-            compute_phi,
         }
     }
     pub fn write(&mut self, result: Option<BlackboardResult>) -> bool {
         match result {
             Some(r) => {
                 let _ = self.file.write(r.to_string().as_bytes());
-                if self.compute_phi {
-                    let _ = self.file.write(format!(",{}\n", r.phi).as_bytes());
-                } else {
-                    let _ = self.file.write(b"\n");
-                }
                 if self.counter == self.flush_frequency {
                     self.file.flush().unwrap();
                     self.counter = 0;
@@ -1,132 +1,590 @@
+use crate::cost::{CostFunction, CostKind};
+use crate::number::Natural;
+use crate::options::{
+    Acceptance, Adversaries, AdversaryMode, AgentOptions, ImitationIntensity, MoveOperator,
+};
+use crate::solver::Solver;
+use crate::strategy::{Blackboard, Hybrid, Imitation, Strategy};
 use bimap::BiHashMap;
+use indicatif::ProgressBar;
+use itertools::{iproduct, Itertools};
 use rand::{seq::SliceRandom, Rng};
-use std::collections::HashSet;
+use rayon::prelude::*;
+use serde::Deserialize;
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    str::FromStr,
+    sync::{Mutex, OnceLock},
+};
+
+pub type Solution = HashMap<char, u32>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operator {
+    Add,
+    // operands[0] - operands[1] - ... = result
+    Subtract,
+    // long multiplication, operands[0] * operands[1] * ... = result
+    Multiply,
+}
+
+impl Operator {
+    fn from_symbol(c: char) -> Option<Operator> {
+        match c {
+            '+' => Some(Operator::Add),
+            '-' => Some(Operator::Subtract),
+            '*' | '×' => Some(Operator::Multiply),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Operator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Operator::Add => write!(f, "+"),
+            Operator::Subtract => write!(f, "-"),
+            Operator::Multiply => write!(f, "*"),
+        }
+    }
+}
+
+// what happens to an assignment that gives the first letter of some word the digit 0
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LeadingZeros {
+    // leading zeros are part of the solutions like any other digit
+    Allow,
+    // no solution has a leading zero, an agent holding one costs the leading zero penalty
+    #[default]
+    Penalize,
+    // no solution has a leading zero, moves that would give an agent one are not made
+    Reject,
+}
+
+impl fmt::Display for LeadingZeros {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LeadingZeros::Allow => write!(f, "allow"),
+            LeadingZeros::Penalize => write!(f, "penalize"),
+            LeadingZeros::Reject => write!(f, "reject"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownLeadingZeros(String);
+
+impl std::error::Error for UnknownLeadingZeros {}
+impl fmt::Display for UnknownLeadingZeros {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "error: unknown leading zero rule '{}', expected allow, penalize or reject",
+            self.0
+        )
+    }
+}
+
+impl FromStr for LeadingZeros {
+    type Err = UnknownLeadingZeros;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "allow" => Ok(LeadingZeros::Allow),
+            "penalize" => Ok(LeadingZeros::Penalize),
+            "reject" => Ok(LeadingZeros::Reject),
+            _ => Err(UnknownLeadingZeros(s.to_string())),
+        }
+    }
+}
+
+// default cost of an assignment that gives some word a leading zero
+pub const LEADING_ZERO_PENALTY: u128 = 100_000_000;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProblemError {
+    MissingEquals,
+    MultipleEquals,
+    MixedOperators(Operator, Operator),
+    TooFewOperands,
+    EmptyWord,
+    InvalidCharacter(char),
+    TooManyLetters { letters: usize, base: u32 },
+    InvalidBase(u32),
+}
+
+impl std::error::Error for ProblemError {}
+impl fmt::Display for ProblemError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ProblemError::MissingEquals => write!(f, "error: problem has no '='"),
+            ProblemError::MultipleEquals => write!(f, "error: problem has more than one '='"),
+            ProblemError::MixedOperators(a, b) => write!(
+                f,
+                "error: problem mixes the operators '{}' and '{}', only one may be used",
+                a, b
+            ),
+            ProblemError::TooFewOperands => {
+                write!(f, "error: problem needs at least two operands")
+            }
+            ProblemError::EmptyWord => write!(f, "error: problem has an empty word"),
+            ProblemError::InvalidCharacter(c) => {
+                write!(f, "error: problem has the non-letter character '{}'", c)
+            }
+            ProblemError::TooManyLetters { letters, base } => write!(
+                f,
+                "error: problem has {} distinct letters but base {} only has {} digits",
+                letters, base, base
+            ),
+            ProblemError::InvalidBase(base) => {
+                write!(f, "error: base must be at least 2, got {}", base)
+            }
+        }
+    }
+}
 
 #[derive(Debug)]
 pub struct Problem {
-    first: String,
-    second: String,
+    operands: Vec<String>,
+    operator: Operator,
     result: String,
+    base: u32,
     letters: HashSet<char>,
+    leading_zeros: LeadingZeros,
+    leading_zero_penalty: u128,
+    // computed on first use and shared by every agent holding a reference to the problem
+    solutions: OnceLock<Vec<Solution>>,
 }
 
 impl Problem {
-    pub fn new(first: &str, second: &str, result: &str) -> Self {
-        let first = first.to_lowercase();
-        let second = second.to_ascii_lowercase();
+    // the cryptarithm operands[0] op operands[1] op ... = result, with digits 0..base
+    pub fn new(
+        operands: &[&str],
+        operator: Operator,
+        result: &str,
+        base: u32,
+    ) -> Result<Self, ProblemError> {
+        if base < 2 {
+            return Err(ProblemError::InvalidBase(base));
+        }
+        if operands.len() < 2 {
+            return Err(ProblemError::TooFewOperands);
+        }
+
+        let operands: Vec<String> = operands.iter().map(|w| w.to_lowercase()).collect();
         let result = result.to_lowercase();
 
-        let mut letters: HashSet<char> = first.chars().collect();
-        letters.extend(second.chars());
-        letters.extend(result.chars());
+        for w in operands.iter().chain([&result]) {
+            if w.is_empty() {
+                return Err(ProblemError::EmptyWord);
+            }
+            if let Some(c) = w.chars().find(|c| !c.is_alphabetic()) {
+                return Err(ProblemError::InvalidCharacter(c));
+            }
+        }
 
-        let mut hs: BiHashMap<char, u32> = BiHashMap::new();
-        hs.extend(
-            letters
-                .iter()
-                .copied()
-                .zip((0..=letters.len()).map(|x| x as u32)),
-        );
+        let mut letters: HashSet<char> = operands.iter().flat_map(|w| w.chars()).collect();
+        letters.extend(result.chars());
+        if letters.len() > base as usize {
+            return Err(ProblemError::TooManyLetters {
+                letters: letters.len(),
+                base,
+            });
+        }
 
-        Problem {
-            first,
-            second,
+        Ok(Problem {
+            operands,
+            operator,
             result,
+            base,
             letters,
+            leading_zeros: LeadingZeros::default(),
+            leading_zero_penalty: LEADING_ZERO_PENALTY,
+            solutions: OnceLock::new(),
+        })
+    }
+
+    // the rule changes which assignments are solutions, so they are solved again
+    pub fn with_leading_zeros(mut self, leading_zeros: LeadingZeros) -> Self {
+        self.leading_zeros = leading_zeros;
+        self.solutions = OnceLock::new();
+        self
+    }
+
+    pub fn with_leading_zero_penalty(mut self, penalty: u128) -> Self {
+        self.leading_zero_penalty = penalty;
+        self
+    }
+
+    // parses an equation such as "SEND + MORE = MONEY", every operator has to be the same
+    pub fn parse(equation: &str, base: u32) -> Result<Self, ProblemError> {
+        let (left, result) = equation
+            .split_once('=')
+            .ok_or(ProblemError::MissingEquals)?;
+        if result.contains('=') {
+            return Err(ProblemError::MultipleEquals);
+        }
+
+        let mut operator: Option<Operator> = None;
+        for op in left.chars().filter_map(Operator::from_symbol) {
+            match operator {
+                Some(o) if o != op => return Err(ProblemError::MixedOperators(o, op)),
+                _ => operator = Some(op),
+            }
+        }
+        let operator = operator.ok_or(ProblemError::TooFewOperands)?;
+
+        let operands: Vec<&str> = left
+            .split(|c| Operator::from_symbol(c).is_some())
+            .map(|w| w.trim())
+            .collect();
+        Problem::new(&operands, operator, result.trim(), base)
+    }
+
+    fn words(&self) -> impl Iterator<Item = &String> {
+        self.operands.iter().chain([&self.result])
+    }
+
+    // number of columns once the words are aligned on the least significant digit
+    pub fn width(&self) -> usize {
+        self.words().map(|w| w.len()).max().unwrap_or(0)
+    }
+
+    // addition and subtraction checked as a sum, a - b - c = r is the same as r + b + c = a
+    fn addition(&self) -> (Vec<&String>, &String) {
+        match self.operator {
+            Operator::Subtract => (
+                [&self.result]
+                    .into_iter()
+                    .chain(self.operands.iter().skip(1))
+                    .collect(),
+                &self.operands[0],
+            ),
+            _ => (self.operands.iter().collect(), &self.result),
         }
     }
+
+    // letters of the addends and of the sum in the column i places from the right
+    fn column(&self, i: usize) -> (Vec<char>, Option<char>) {
+        let (addends, sum) = self.addition();
+        let addends = addends
+            .iter()
+            .filter_map(|w| w.chars().rev().nth(i))
+            .collect();
+        (addends, sum.chars().rev().nth(i))
+    }
+
+    // every letter in the columns up to i places from the right
+    fn columns_up_to(&self, i: usize) -> Vec<char> {
+        self.words()
+            .flat_map(|w| w.chars().rev().take(i + 1))
+            .collect()
+    }
+
+    // letters a hint for the column i places from the right assigns
+    // distinct letters written in column i of the equation as it is given
+    fn letters_in_column(&self, i: usize) -> Vec<char> {
+        self.words()
+            .filter_map(|w| w.chars().rev().nth(i))
+            .unique()
+            .collect()
+    }
+
+    fn column_letters(&self, i: usize) -> Vec<char> {
+        match self.operator {
+            Operator::Add | Operator::Subtract => {
+                let (addends, sum) = self.column(i);
+                addends.into_iter().chain(sum).collect()
+            }
+            // the lowest i + 1 digits of a product only depend on the lowest i + 1 digits
+            // of the operands, so a column is checked together with the ones below it
+            Operator::Multiply => self.columns_up_to(i),
+        }
+    }
+
+    // whether the digits given to the letters of column i can be part of a solution
+    pub(crate) fn is_column_consistent(&self, i: usize, digit: impl Fn(char) -> u32) -> bool {
+        let base = self.base;
+        match self.operator {
+            Operator::Add | Operator::Subtract => {
+                let (addends, sum) = self.column(i);
+                let total: u32 = addends.into_iter().map(&digit).sum();
+                // an addend longer than the sum is matched against a zero
+                let sum = sum.map_or(0, &digit);
+
+                // n addends can carry anything from 0 to n - 1 into a column
+                let carries = 0..self.addition().0.len() as u32;
+                carries.into_iter().any(|c| (total + c) % base == sum)
+            }
+            Operator::Multiply => {
+                let truncated = |w: &String| {
+                    Natural::from_digits(base, w.chars().rev().take(i + 1).map(&digit))
+                };
+                let operands = self.operands.iter().map(truncated);
+                Problem::truncated_product(base, i + 1, operands) == truncated(&self.result)
+            }
+        }
+    }
+
+    // lowest n digits of the product of operands that are already truncated to n digits, the
+    // lowest n digits of a product only depend on the lowest n digits of its factors
+    pub(crate) fn truncated_product(
+        base: u32,
+        n: usize,
+        operands: impl IntoIterator<Item = Natural>,
+    ) -> Natural {
+        // while the modulus fits into a u64 the product of two values fits into a u128
+        match (base as u64).checked_pow(n as u32) {
+            Some(modulus) => {
+                let modulus = modulus as u128;
+                let product = operands
+                    .into_iter()
+                    .fold(1u128, |p, o| p * o.to_u128().unwrap() % modulus);
+                Natural::from_u128(base, product)
+            }
+            None => operands
+                .into_iter()
+                .fold(Natural::one(base), |p, o| p.mul(&o).truncated(n)),
+        }
+    }
+
+    // columns whose check rejects some assignment of their letters, the others never give a
+    // useful hint
+    pub fn hint_columns(&self) -> usize {
+        (0..self.width())
+            .filter(|i| {
+                let letters: Vec<char> = self.column_letters(*i).into_iter().unique().collect();
+                (0..self.base).permutations(letters.len()).any(|digits| {
+                    let digit = |c: char| digits[letters.iter().position(|l| *l == c).unwrap()];
+                    !self.is_column_consistent(*i, digit)
+                })
+            })
+            .count()
+    }
+
+    // all assignments that solve the problem, leading letters are only zero if that is allowed
+    pub fn solve(&self) -> Vec<Solution> {
+        let operands: Vec<&str> = self.operands.iter().map(|w| w.as_str()).collect();
+        let allow_leading_zeros = self.leading_zeros == LeadingZeros::Allow;
+        Solver::new(
+            &operands,
+            self.operator,
+            &self.result,
+            self.base,
+            allow_leading_zeros,
+        )
+        .solve()
+    }
+
+    // same as solve, but computed only once
+    pub fn solutions(&self) -> &[Solution] {
+        self.solutions.get_or_init(|| self.solve())
+    }
+
+    pub fn solution_count(&self) -> usize {
+        self.solutions().len()
+    }
+
+    pub fn is_solvable(&self) -> bool {
+        self.solution_count() > 0
+    }
+
+    pub fn base(&self) -> u32 {
+        self.base
+    }
+
+    pub fn letter_count(&self) -> usize {
+        self.letters.len()
+    }
+
+    pub fn leading_zeros(&self) -> LeadingZeros {
+        self.leading_zeros
+    }
+
+    pub fn leading_zero_penalty(&self) -> u128 {
+        self.leading_zero_penalty
+    }
+
+    // whether the letter starts some word
+    fn is_leading(&self, letter: char) -> bool {
+        self.words().any(|w| w.starts_with(letter))
+    }
+
+    // number of injective assignments of the n letters to the k digits, k!/(k-n)!
+    pub fn search_space(&self) -> f64 {
+        let n = self.letters.len() as u32;
+        (self.base - n + 1..=self.base).map(|k| k as f64).product()
+    }
 }
 
 impl Default for Problem {
     fn default() -> Self {
-        Problem::new("DONALD", "GERALD", "ROBERT")
+        Problem::new(&["DONALD", "GERALD"], Operator::Add, "ROBERT", 10).unwrap()
     }
 }
 
-#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
-pub struct LetterAssignment {
-    letter: char,
-    value: u32,
-}
+impl FromStr for Problem {
+    type Err = ProblemError;
 
-#[derive(Debug, Clone, Copy)]
-pub enum Hint {
-    One(LetterAssignment),
-    Two(LetterAssignment, LetterAssignment),
-    Three(LetterAssignment, LetterAssignment, LetterAssignment),
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Problem::parse(s, 10)
+    }
 }
 
-impl PartialEq for Hint {
-    fn eq(&self, other: &Self) -> bool {
-        match (self, other) {
-            (Hint::One(a), Hint::One(a1)) => a == a1,
-            (Hint::Two(a, b), Hint::Two(c, d)) => (a == c && b == d) || (a == d && b == c),
-            (Hint::Three(a, b, c), Hint::Three(a1, b1, c1)) => {
-                let h: HashSet<LetterAssignment> = [*a, *b, *c].iter().copied().collect();
-                let h1: HashSet<LetterAssignment> = [*a1, *b1, *c1].iter().copied().collect();
-                h == h1
-            }
-            _ => false,
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} = {}",
+            self.operands
+                .iter()
+                .map(|w| w.to_uppercase())
+                .join(&format!(" {} ", self.operator)),
+            self.result.to_uppercase()
+        )?;
+        if self.base != 10 {
+            write!(f, " (base {})", self.base)?;
         }
+        Ok(())
     }
 }
 
-impl Hint {
-    fn is_correct(&self) -> bool {
-        let p = Problem::default();
-        let mut a: Agent = Agent::new(&p, false);
-        a.assign_random();
-        a.assimilate_hint(self);
-        if *a.assignment.get_by_left(&'d').unwrap() == 5
-            && *a.assignment.get_by_left(&'g').unwrap() == 1
-            && *a.assignment.get_by_left(&'r').unwrap() == 7
-        {
-            return true;
-        }
-        if *a.assignment.get_by_left(&'o').unwrap() == 2
-            && *a.assignment.get_by_left(&'e').unwrap() == 9
-        {
-            return true;
-        }
-        if *a.assignment.get_by_left(&'n').unwrap() == 6
-            && *a.assignment.get_by_left(&'r').unwrap() == 7
-            && *a.assignment.get_by_left(&'b').unwrap() == 3
-        {
-            return true;
-        }
-        if *a.assignment.get_by_left(&'a').unwrap() == 4
-            && *a.assignment.get_by_left(&'e').unwrap() == 9
-        {
-            return true;
-        }
-        if *a.assignment.get_by_left(&'l').unwrap() == 8
-            && *a.assignment.get_by_left(&'r').unwrap() == 7
-        {
-            return true;
+// problem section of the config files, defaults to DONALD + GERALD = ROBERT
+#[derive(Debug, Deserialize, Clone)]
+pub struct ProblemConfigData {
+    pub equation: String,
+    #[serde(default = "default_base")]
+    pub base: u32,
+    #[serde(default)]
+    pub leading_zeros: LeadingZeros,
+    #[serde(default = "default_leading_zero_penalty")]
+    pub leading_zero_penalty: u64,
+}
+
+fn default_base() -> u32 {
+    10
+}
+
+fn default_leading_zero_penalty() -> u64 {
+    LEADING_ZERO_PENALTY as u64
+}
+
+impl ProblemConfigData {
+    pub fn build(&self) -> Result<Problem, ProblemError> {
+        Ok(Problem::parse(&self.equation, self.base)?
+            .with_leading_zeros(self.leading_zeros)
+            .with_leading_zero_penalty(self.leading_zero_penalty as u128))
+    }
+
+    // every model needs a problem with a solution
+    pub fn validate(&self) -> Result<(), String> {
+        if !self.build().map_err(|e| e.to_string())?.is_solvable() {
+            return Err("problem has no solution".to_string());
         }
-        if *a.assignment.get_by_left(&'d').unwrap() == 5
-            && *a.assignment.get_by_left(&'t').unwrap() == 0
-        {
-            return true;
+        Ok(())
+    }
+
+    pub fn log(&self) {
+        let problem = self
+            .build()
+            .expect("problem is validated when the config is loaded");
+        println!("    - problem:         {}", problem);
+        println!("    - solutions:       {}", problem.solution_count());
+        println!("    - search_space:    {}", problem.search_space());
+        println!("    - leading_zeros:   {}", problem.leading_zeros());
+        println!("    - zero_penalty:    {}", problem.leading_zero_penalty());
+    }
+}
+
+impl Default for ProblemConfigData {
+    fn default() -> Self {
+        ProblemConfigData {
+            equation: "DONALD + GERALD = ROBERT".to_string(),
+            base: default_base(),
+            leading_zeros: LeadingZeros::default(),
+            leading_zero_penalty: default_leading_zero_penalty(),
         }
+    }
+}
 
-        false
+// random hints an adversary draws before it gives up looking for a false one
+const FORGE_ATTEMPTS: usize = 100;
+
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct LetterAssignment {
+    pub letter: char,
+    pub value: u32,
+}
+
+// the letters of one column together with their digits, kept sorted by letter so that
+// two hints holding the same assignments compare equal
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Hint(Vec<LetterAssignment>);
+
+impl Hint {
+    pub fn new(assignments: impl IntoIterator<Item = LetterAssignment>) -> Self {
+        let mut assignments: Vec<LetterAssignment> = assignments.into_iter().collect();
+        assignments.sort_unstable_by_key(|a| a.letter);
+        assignments.dedup();
+        Hint(assignments)
+    }
+    pub fn assignments(&self) -> &[LetterAssignment] {
+        &self.0
+    }
+    // a hint is correct if every letter in it has the value it takes in some solution of the problem
+    pub fn is_correct(&self, problem: &Problem) -> bool {
+        problem.solutions().iter().any(|solution| {
+            self.assignments()
+                .iter()
+                .all(|a| solution.get(&a.letter) == Some(&a.value))
+        })
+    }
+    // number of columns whose letters all have a digit in the hint and are consistent with
+    // them, a hint an agent found covers at least its own column
+    pub fn quality(&self, problem: &Problem) -> usize {
+        let digit = |c: char| self.0.iter().find(|a| a.letter == c).map(|a| a.value);
+        (0..problem.width())
+            .filter(|i| {
+                problem
+                    .column_letters(*i)
+                    .iter()
+                    .all(|c| digit(*c).is_some())
+                    && problem.is_column_consistent(*i, |c| digit(c).unwrap())
+            })
+            .count()
     }
 }
 
 #[derive(Debug, Clone)]
 pub struct Agent<'a> {
-    problem: &'a Problem,
+    pub(crate) problem: &'a Problem,
     hints: Vec<Hint>,
+    // hint taken from the environment, followed by the next act
+    observed: Option<Hint>,
+    // whether the next act judges the observed hint, only if the board reads votes
+    judge: bool,
+    // followed hint and whether it lowered the cost, voted on by the next contribution
+    vote: Option<(Hint, bool)>,
     assignment: BiHashMap<char, u32>,
-    pub cost: u32,
+    options: AgentOptions,
+    // cost the acceptance rule judges moves by
+    cost_function: CostKind,
+    // elementary moves made so far, the clock of the annealing schedules
+    moves: u32,
+    // misinformation the agent posts, None for an honest agent
+    adversary: Option<Adversaries>,
+    pub cost: u128,
+    // whether cost belongs to the current assignment
+    costed: bool,
     pub correct_hints: u32,
     pub total_hints: u32,
     pub compute_phi: bool,
 }
 
 impl<'a> Agent<'a> {
-    pub fn new(problem: &'a Problem, compute_phi: bool) -> Self {
+    pub fn new(problem: &'a Problem, options: AgentOptions, compute_phi: bool) -> Self {
         let mut assignment: BiHashMap<char, u32> = BiHashMap::new();
         assignment.extend(
             problem
@@ -138,202 +596,360 @@ impl<'a> Agent<'a> {
         Agent {
             problem,
             hints: Vec::new(),
+            observed: None,
+            judge: false,
+            vote: None,
             assignment,
+            options,
+            cost_function: CostKind::default(),
+            moves: 0,
+            adversary: None,
             cost: 0,
+            costed: false,
             correct_hints: 0,
             total_hints: 0,
             compute_phi,
         }
     }
+
+    pub fn with_cost_function(mut self, cost_function: CostKind) -> Self {
+        self.cost_function = cost_function;
+        self
+    }
+
+    pub fn with_adversary(mut self, adversaries: Adversaries) -> Self {
+        self.adversary = Some(adversaries);
+        self
+    }
 }
 
 impl<'a> Agent<'a> {
     pub fn assign_random(&mut self) {
-        let mut digits: Vec<u32> = (0..=9).collect();
+        let mut digits: Vec<u32> = (0..self.problem.base).collect();
         for (l, _) in self.assignment.clone().iter() {
             let to_remove = rand::thread_rng().gen_range(0..digits.len());
             let random = digits.remove(to_remove);
             self.assignment.insert(*l, random);
         }
+        self.costed = false;
+
+        // a rejecting agent never holds a leading zero, so the letter on 0 trades places with a
+        // letter that may be zero, or moves to a free digit
+        if self.problem.leading_zeros == LeadingZeros::Reject {
+            let zero = self.assignment.get_by_right(&0).copied();
+            if let Some(letter) = zero.filter(|l| self.problem.is_leading(*l)) {
+                let digits: Vec<u32> = (1..self.problem.base)
+                    .filter(|d| {
+                        self.assignment
+                            .get_by_right(d)
+                            .is_none_or(|l| !self.problem.is_leading(*l))
+                    })
+                    .collect();
+                if let Some(value) = digits.choose(&mut rand::thread_rng()) {
+                    self.swap_letter_assignment(&LetterAssignment {
+                        letter,
+                        value: *value,
+                    });
+                }
+            }
+        }
     }
     pub fn find_hints(&mut self) {
         self.hints.clear();
 
-        for i in 0..self.problem.first.len() {
-            // Get character at index i
-            let a1 = self.problem.first.chars().nth(i).unwrap();
-            let a2 = self.problem.second.chars().nth(i).unwrap();
-            let r = self.problem.result.chars().nth(i).unwrap();
-
-            // get digit assigned to that character
-            let a1_d = self.assignment.get_by_left(&a1).unwrap();
-            let a2_d = self.assignment.get_by_left(&a2).unwrap();
-            let r_d = self.assignment.get_by_left(&r).unwrap();
-
-            let cond1 = (a1_d + a2_d) % 10 == *r_d;
-            let cond2 = (a1_d + a2_d + 1) % 10 == *r_d;
-
-            if cond1 || cond2 {
-                let hint: HashSet<LetterAssignment> = [
-                    LetterAssignment {
-                        letter: a1,
-                        value: *a1_d,
-                    },
-                    LetterAssignment {
-                        letter: a2,
-                        value: *a2_d,
-                    },
-                    LetterAssignment {
-                        letter: r,
-                        value: *r_d,
-                    },
-                ]
+        // columns are aligned on the least significant digit, i counts from the right
+        for i in 0..self.problem.width() {
+            let digit = |c: char| *self.assignment.get_by_left(&c).unwrap();
+            // a leading zero can not be part of a solution unless it is allowed
+            let leading_zero = |c: &char| digit(*c) == 0 && self.problem.is_leading(*c);
+            let letters = self.problem.column_letters(i);
+            if self.problem.leading_zeros != LeadingZeros::Allow && letters.iter().any(leading_zero)
+            {
+                continue;
+            }
+            if self.problem.is_column_consistent(i, digit) {
+                let hint = Hint::new(letters.into_iter().map(|c| LetterAssignment {
+                    letter: c,
+                    value: digit(c),
+                }));
+                // columns with the same letters give the same hint, which is held once
+                if !self.hints.contains(&hint) {
+                    self.hints.push(hint);
+                }
+            }
+        }
+    }
+    // the hint the agent posts and whether it is forged, None if it has nothing new to post
+    fn choose_post(&self, blackboard: &Blackboard) -> Option<(Hint, bool)> {
+        let honest = || {
+            let novel: Vec<&Hint> = self
+                .hints
                 .iter()
-                .copied()
+                .filter(|hint| !blackboard.contains(hint))
                 .collect();
-                let hint: Vec<LetterAssignment> = hint.iter().copied().collect();
-                let hint = match hint.len() {
-                    1 => Hint::One(*hint.get(0).unwrap()),
-                    2 => Hint::Two(*hint.get(0).unwrap(), *hint.get(1).unwrap()),
-                    3 => Hint::Three(
-                        *hint.get(0).unwrap(),
-                        *hint.get(1).unwrap(),
-                        *hint.get(2).unwrap(),
-                    ),
-                    _ => unreachable!(),
-                };
-                self.hints.push(hint);
-                // println!("fount hint: {:?}", hint);
+            novel
+                .choose(&mut rand::thread_rng())
+                .map(|hint| (*hint).clone())
+        };
+        let (hint, forged) = match self.adversary {
+            None => (honest()?, false),
+            Some(Adversaries {
+                mode: AdversaryMode::Noisy,
+                e,
+                ..
+            }) => {
+                let hint = honest()?;
+                if rand::thread_rng().gen_bool(e) {
+                    let corrupted = self.corrupt(&hint);
+                    let forged = corrupted != hint;
+                    (corrupted, forged)
+                } else {
+                    (hint, false)
+                }
+            }
+            Some(Adversaries { mode, .. }) => {
+                let only_false = mode == AdversaryMode::False;
+                (self.forge(only_false, &mut rand::thread_rng())?, true)
+            }
+        };
+        (!blackboard.contains(&hint)).then_some((hint, forged))
+    }
+    // the letters of a random column with random digits, drawn again until they satisfy the column
+    // but are not part of any solution if only a false hint will do
+    fn forge(&self, only_false: bool, rng: &mut impl Rng) -> Option<Hint> {
+        let digits: Vec<u32> = (0..self.problem.base).collect();
+        for _ in 0..FORGE_ATTEMPTS {
+            let column = rng.gen_range(0..self.problem.width());
+            let letters: Vec<char> = self
+                .problem
+                .column_letters(column)
+                .into_iter()
+                .unique()
+                .collect();
+            let values = digits.choose_multiple(rng, letters.len());
+            let hint = Hint::new(letters.into_iter().zip(values).map(|(letter, value)| {
+                LetterAssignment {
+                    letter,
+                    value: *value,
+                }
+            }));
+            let digit = |c: char| {
+                hint.assignments()
+                    .iter()
+                    .find(|a| a.letter == c)
+                    .unwrap()
+                    .value
+            };
+            if !only_false
+                || self.problem.is_column_consistent(column, digit)
+                    && !hint.is_correct(self.problem)
+            {
+                return Some(hint);
             }
         }
+        None
     }
-    pub fn pick_and_replace(&mut self, blackboard: &mut Vec<Hint>) {
-        let novel: Vec<&Hint> = self
-            .hints
-            .iter()
-            .filter(|hint| !blackboard.contains(*hint))
+    // the hint with one random letter moved to a digit the hint does not use, unchanged if it
+    // uses all of them
+    fn corrupt(&self, hint: &Hint) -> Hint {
+        let mut rng = rand::thread_rng();
+        let mut assignments = hint.assignments().to_vec();
+        let free: Vec<u32> = (0..self.problem.base)
+            .filter(|d| assignments.iter().all(|a| a.value != *d))
             .collect();
-        if novel.is_empty() {
-            return;
+        if let (Some(a), Some(d)) = (assignments.choose_mut(&mut rng), free.choose(&mut rng)) {
+            a.value = *d;
         }
-        let selected = *novel.choose(&mut rand::thread_rng()).unwrap();
+        Hint::new(assignments)
+    }
+    pub fn pick_and_replace(&mut self, blackboard: &mut Blackboard) {
+        let Some((selected, forged)) = self.choose_post(blackboard) else {
+            return;
+        };
 
-        if blackboard.len() == blackboard.capacity() {
+        if blackboard.is_full() {
             let different: Vec<usize> = blackboard
+                .hints()
                 .iter()
                 .enumerate()
                 .filter(|(_, hint)| !self.hints.contains(hint))
                 .map(|(i, _)| i)
                 .collect();
-            if different.is_empty() {
+            let Some(to_replace) = blackboard.victim(self.problem, &different) else {
                 return;
+            };
+            blackboard.remove(to_replace);
+        }
+        blackboard.post(selected, forged, self.problem);
+    }
+    pub fn solution(&self) -> Solution {
+        self.assignment.iter().map(|(l, d)| (*l, *d)).collect()
+    }
+    pub(crate) fn digit(&self, letter: char) -> u32 {
+        *self.assignment.get_by_left(&letter).unwrap()
+    }
+    fn value(&self, word: &str) -> Option<u128> {
+        let base = self.problem.base as u128;
+        word.chars().try_fold(0u128, |v, l| {
+            let digit = *self.assignment.get_by_left(&l).unwrap() as u128;
+            v.checked_mul(base)?.checked_add(digit)
+        })
+    }
+    fn wide_value(&self, word: &str) -> Natural {
+        let digits = word
+            .chars()
+            .rev()
+            .map(|l| *self.assignment.get_by_left(&l).unwrap());
+        Natural::from_digits(self.problem.base, digits)
+    }
+    // both sides of the equation, a - b - c = r is compared as r + b + c = a so nothing is
+    // negative, None once a side does not fit into a u128
+    pub(crate) fn sides(&self) -> Option<(u128, u128)> {
+        match self.problem.operator {
+            Operator::Add | Operator::Subtract => {
+                let (addends, sum) = self.problem.addition();
+                let total = addends
+                    .iter()
+                    .try_fold(0u128, |t, w| t.checked_add(self.value(w)?))?;
+                Some((total, self.value(sum)?))
+            }
+            Operator::Multiply => {
+                let product = self
+                    .problem
+                    .operands
+                    .iter()
+                    .try_fold(1u128, |p, w| p.checked_mul(self.value(w)?))?;
+                Some((product, self.value(&self.problem.result)?))
             }
-            let to_replace = different.choose(&mut rand::thread_rng()).unwrap();
-            blackboard.remove(*to_replace);
-            blackboard.push(*selected);
-        } else {
-            blackboard.push(*selected);
         }
     }
-    pub fn make_move(&mut self, blackboard: &mut Vec<Hint>) {
-        if !blackboard.is_empty() {
-            let random_hint = blackboard.choose(&mut rand::thread_rng()).unwrap();
-            if self.compute_phi {
-                self.total_hints += 1;
-                if random_hint.is_correct() {
-                    self.correct_hints += 1;
-                }
+    // same as sides without any limit on the length of the words
+    pub(crate) fn wide_sides(&self) -> (Natural, Natural) {
+        let base = self.problem.base;
+        match self.problem.operator {
+            Operator::Add | Operator::Subtract => {
+                let (addends, sum) = self.problem.addition();
+                let total = addends.iter().fold(Natural::from_digits(base, []), |t, w| {
+                    t.add(&self.wide_value(w))
+                });
+                (total, self.wide_value(sum))
             }
-            if !self.hints.contains(random_hint) {
-                self.assimilate_hint(random_hint);
-                return;
+            Operator::Multiply => {
+                let product = self
+                    .problem
+                    .operands
+                    .iter()
+                    .fold(Natural::one(base), |p, w| p.mul(&self.wide_value(w)));
+                (product, self.wide_value(&self.problem.result))
             }
         }
-        self.elementary_move();
+    }
+    fn has_leading_zero(&self) -> bool {
+        self.problem
+            .words()
+            .any(|w| self.digit(w.chars().next().unwrap()) == 0)
     }
     pub fn is_solved(&self) -> bool {
-        let base: u32 = 10;
-
-        let mut first_n: u32 = 0;
-        for (i, l) in self.problem.first.chars().rev().enumerate() {
-            first_n += base.pow(i as u32) * self.assignment.get_by_left(&l).unwrap();
-        }
-
-        let mut second_n: u32 = 0;
-        for (i, l) in self.problem.second.chars().rev().enumerate() {
-            second_n += base.pow(i as u32) * self.assignment.get_by_left(&l).unwrap();
+        if self.problem.leading_zeros != LeadingZeros::Allow && self.has_leading_zero() {
+            return false;
         }
-
-        let mut result_n: u32 = 0;
-        for (i, l) in self.problem.result.chars().rev().enumerate() {
-            result_n += base.pow(i as u32) * self.assignment.get_by_left(&l).unwrap();
+        match self.sides() {
+            Some((left, right)) => left == right,
+            None => {
+                let (left, right) = self.wide_sides();
+                left == right
+            }
         }
-
-        first_n + second_n == result_n
     }
-    pub fn compute_cost(&mut self) {
-        let base: u32 = 10;
-
-        if *self
-            .assignment
-            .get_by_left(&self.problem.first.chars().next().unwrap())
-            .unwrap()
-            == 0u32
-            || *self
-                .assignment
-                .get_by_left(&self.problem.second.chars().next().unwrap())
-                .unwrap()
-                == 0u32
-            || *self
-                .assignment
-                .get_by_left(&self.problem.result.chars().next().unwrap())
-                .unwrap()
-                == 0u32
-        {
-            self.cost = base.pow(8);
+    pub(crate) fn compute_cost(&mut self, function: &dyn CostFunction) {
+        if self.problem.leading_zeros == LeadingZeros::Penalize && self.has_leading_zero() {
+            self.cost = self.problem.leading_zero_penalty;
             return;
         }
 
-        let mut first_n: u32 = 0;
-        for (i, l) in self.problem.first.chars().rev().enumerate() {
-            first_n += base.pow(i as u32) * self.assignment.get_by_left(&l).unwrap();
+        self.cost = function.cost(self);
+        self.costed = true;
+    }
+    // the cost of the current assignment, only computed if the assignment changed since
+    fn current_cost(&mut self, function: &dyn CostFunction) -> u128 {
+        if !self.costed {
+            self.compute_cost(function);
         }
+        self.cost
+    }
+    pub fn swap_letter_assignment(&mut self, desired: &LetterAssignment) {
+        let current_value = *self.assignment.get_by_left(&desired.letter).unwrap();
+        let other_key = self.assignment.get_by_right(&desired.value).copied();
 
-        let mut second_n: u32 = 0;
-        for (i, l) in self.problem.second.chars().rev().enumerate() {
-            second_n += base.pow(i as u32) * self.assignment.get_by_left(&l).unwrap();
+        // a rejecting agent does not make a move that puts a leading letter on 0
+        if self.problem.leading_zeros == LeadingZeros::Reject {
+            let leading_zero =
+                |letter: char, value: u32| value == 0 && self.problem.is_leading(letter);
+            if leading_zero(desired.letter, desired.value)
+                || other_key.is_some_and(|o| leading_zero(o, current_value))
+            {
+                return;
+            }
         }
 
-        let mut result_n: u32 = 0;
-        for (i, l) in self.problem.result.chars().rev().enumerate() {
-            result_n += base.pow(i as u32) * self.assignment.get_by_left(&l).unwrap();
+        // with fewer letters than digits the desired digit may be free, then the letter just moves
+        if let Some(other_key) = other_key {
+            self.assignment.insert(other_key, current_value);
         }
-
-        self.cost = ((result_n as i32) - ((first_n + second_n) as i32)).unsigned_abs();
-    }
-    pub fn swap_letter_assignment(&mut self, desired: &LetterAssignment) {
-        let other_key = *self.assignment.get_by_right(&desired.value).unwrap();
-        let current_value = *self.assignment.get_by_left(&desired.letter).unwrap();
-        self.assignment.insert(other_key, current_value);
         self.assignment.insert(desired.letter, desired.value);
+        self.costed = false;
     }
     pub fn assimilate_hint(&mut self, hint: &Hint) {
-        match hint {
-            Hint::One(a) => {
-                self.swap_letter_assignment(a);
-            }
-            Hint::Two(a, b) => {
-                self.swap_letter_assignment(a);
-                self.swap_letter_assignment(b);
+        for a in hint.assignments() {
+            self.swap_letter_assignment(a);
+        }
+    }
+    // a move the acceptance rule turns down is undone
+    pub fn elementary_move(&mut self) {
+        if self.options.acceptance == Acceptance::Always {
+            self.propose_move();
+            return;
+        }
+        let function = self.cost_function.function();
+        let (assignment, cost) = (self.assignment.clone(), self.current_cost(function));
+        self.propose_move();
+        self.compute_cost(function);
+        self.moves = self.moves.saturating_add(1);
+        if !self.accepts(cost, self.cost) {
+            self.assignment = assignment;
+            self.cost = cost;
+        }
+    }
+    fn accepts(&self, before: u128, after: u128) -> bool {
+        if after <= before {
+            return true;
+        }
+        match self.options.acceptance {
+            Acceptance::Always => true,
+            Acceptance::Greedy => false,
+            _ => {
+                let temperature = self.options.temperature(self.moves);
+                let probability = (-((after - before) as f64) / temperature).exp();
+                rand::thread_rng().gen_bool(probability)
             }
-            Hint::Three(a, b, c) => {
-                self.swap_letter_assignment(a);
-                self.swap_letter_assignment(b);
-                self.swap_letter_assignment(c);
+        }
+    }
+    fn propose_move(&mut self) {
+        match self.options.move_operator {
+            MoveOperator::Reassign => self.reassign_move(),
+            MoveOperator::Swap => self.rotate_move(2),
+            MoveOperator::Rotate => self.rotate_move(self.options.rotation_size),
+            MoveOperator::ColumnScramble => self.column_scramble_move(),
+            MoveOperator::Restart => {
+                if rand::thread_rng().gen_bool(self.options.restart_probability) {
+                    self.assign_random();
+                } else {
+                    self.reassign_move();
+                }
             }
         }
     }
-    pub fn elementary_move(&mut self) {
+    fn reassign_move(&mut self) {
         let rand_letter = *self
             .assignment
             .iter()
@@ -341,144 +957,583 @@ impl<'a> Agent<'a> {
             .collect::<Vec<char>>()
             .choose(&mut rand::thread_rng())
             .unwrap();
-        let mut rand_digit = rand::thread_rng().gen_range(0..=9);
+        let base = self.problem.base;
+        let mut rand_digit = rand::thread_rng().gen_range(0..base);
         if rand_digit == *self.assignment.get_by_left(&rand_letter).unwrap() {
-            rand_digit = (rand_digit + 1) % 10;
+            rand_digit = (rand_digit + 1) % base;
         }
         self.swap_letter_assignment(&LetterAssignment {
             letter: rand_letter,
             value: rand_digit,
         });
     }
-    pub fn imitate(&mut self, best: &Agent) {
-        let letters: Vec<char> = self.problem.letters.iter().cloned().collect();
-        let random_letter = letters.choose(&mut rand::thread_rng()).unwrap();
-        self.assimilate_hint(&Hint::One(LetterAssignment {
-            letter: *random_letter,
-            value: *best.assignment.get_by_left(random_letter).unwrap(),
-        }));
+    // k random letters each take the digit of the next one
+    fn rotate_move(&mut self, k: usize) {
+        let letters: Vec<char> = self.problem.letters.iter().copied().collect();
+        let letters: Vec<char> = letters
+            .choose_multiple(&mut rand::thread_rng(), k)
+            .copied()
+            .collect();
+        let digits = letters.iter().cycle().skip(1).map(|l| self.digit(*l));
+        let moved = letters.iter().copied().zip(digits).collect();
+        self.move_letters(moved);
+    }
+    fn column_scramble_move(&mut self) {
+        let mut rng = rand::thread_rng();
+        let i = rng.gen_range(0..self.problem.width());
+        let letters = self.problem.letters_in_column(i);
+        let free = (0..self.problem.base).filter(|d| !self.assignment.contains_right(d));
+        let mut digits: Vec<u32> = letters.iter().map(|l| self.digit(*l)).chain(free).collect();
+        digits.shuffle(&mut rng);
+        let moved = letters.into_iter().zip(digits).collect();
+        self.move_letters(moved);
+    }
+    // every digit given out is either free or held by one of the moved letters
+    fn move_letters(&mut self, moved: Vec<(char, u32)>) {
+        if self.problem.leading_zeros == LeadingZeros::Reject
+            && moved
+                .iter()
+                .any(|(l, d)| *d == 0 && self.problem.is_leading(*l))
+        {
+            return;
+        }
+        for (l, _) in moved.iter() {
+            self.assignment.remove_by_left(l);
+        }
+        self.assignment.extend(moved);
+        self.costed = false;
+    }
+    // the observed hint, or an elementary move if there is none
+    fn follow_observation(&mut self) {
+        match self.observed.take() {
+            Some(hint) => self.assimilate_hint(&hint),
+            None => self.elementary_move(),
+        }
+    }
+}
+
+impl<'a> Strategy<Blackboard> for Agent<'a> {
+    // the hint the read policy of the blackboard picks is followed unless the agent found it itself
+    fn observe(&mut self, blackboard: &Blackboard) {
+        self.observed = None;
+        self.judge = blackboard.reads_votes();
+        let Some(i) = blackboard.sample() else {
+            return;
+        };
+        let hint = blackboard.read(i);
+        if self.compute_phi {
+            self.total_hints += 1;
+            if hint.is_correct(self.problem) {
+                self.correct_hints += 1;
+            }
+        }
+        if !self.hints.contains(hint) {
+            self.observed = Some(hint.clone());
+        }
+    }
+    // a followed hint is judged by the change of the cost, one that does not change it gets no vote
+    fn act(&mut self) {
+        let Some(hint) = self.observed.take() else {
+            self.elementary_move();
+            return;
+        };
+        if !self.judge {
+            self.assimilate_hint(&hint);
+            return;
+        }
+        let function = self.cost_function.function();
+        let before = self.current_cost(function);
+        self.assimilate_hint(&hint);
+        self.compute_cost(function);
+        self.vote = (self.cost != before).then_some((hint, self.cost < before));
+    }
+    fn contribute(&mut self, blackboard: &mut Blackboard) {
+        if let Some((hint, up)) = self.vote.take() {
+            blackboard.vote(&hint, up);
+        }
+        blackboard.release(&self.hints);
+        self.find_hints();
+        blackboard.hold(&self.hints);
+        self.pick_and_replace(blackboard);
+    }
+    fn is_solved(&self) -> bool {
+        Agent::is_solved(self)
+    }
+    fn assignment(&self) -> Solution {
+        self.solution()
+    }
+    fn hint_accuracy(&self) -> f64 {
+        (self.correct_hints as f64) / (self.total_hints as f64)
+    }
+}
+
+impl<'a> Strategy<Imitation> for Agent<'a> {
+    // with probability p the letters the intensity picks take their digits in the model
+    fn observe(&mut self, imitation: &Imitation) {
+        self.observed = imitation
+            .model
+            .as_ref()
+            .filter(|_| rand::thread_rng().gen_bool(imitation.p))
+            .map(|model| {
+                let mut rng = rand::thread_rng();
+                let letters: Vec<char> = self.problem.letters.iter().cloned().collect();
+                let copied: Vec<char> = match imitation.intensity {
+                    ImitationIntensity::Letters { k } => {
+                        letters.choose_multiple(&mut rng, k).copied().collect()
+                    }
+                    ImitationIntensity::Column => {
+                        let i = rng.gen_range(0..self.problem.width());
+                        self.problem.letters_in_column(i)
+                    }
+                    ImitationIntensity::Independent { q } => {
+                        letters.into_iter().filter(|_| rng.gen_bool(q)).collect()
+                    }
+                };
+                Hint::new(copied.into_iter().map(|letter| LetterAssignment {
+                    letter,
+                    value: model[&letter],
+                }))
+            });
+    }
+    fn act(&mut self) {
+        self.follow_observation();
+    }
+    fn contribute(&mut self, imitation: &mut Imitation) {
+        imitation.reported_cost = self.current_cost(self.cost_function.function());
+    }
+    // the cost of a solution is 0, which is cheaper to check first
+    fn is_solved(&self) -> bool {
+        self.cost == 0 && Agent::is_solved(self)
+    }
+    fn assignment(&self) -> Solution {
+        self.solution()
+    }
+}
+
+impl<'a> Strategy<Hybrid> for Agent<'a> {
+    // with probability q the agent reads the board, if that gives it nothing to follow it imitates
+    // the model with probability p
+    fn observe(&mut self, hybrid: &Hybrid) {
+        self.observed = None;
+        self.judge = hybrid.blackboard.reads_votes();
+        if rand::thread_rng().gen_bool(hybrid.q) {
+            <Self as Strategy<Blackboard>>::observe(self, &hybrid.blackboard);
+        }
+        if self.observed.is_none() {
+            <Self as Strategy<Imitation>>::observe(self, &hybrid.imitation);
+        }
+    }
+    // a copy of the model is judged like a hint, the board ignores the vote if it does not hold it
+    fn act(&mut self) {
+        <Self as Strategy<Blackboard>>::act(self);
+    }
+    // agents that read the board share their hints on it, every agent reports its cost
+    fn contribute(&mut self, hybrid: &mut Hybrid) {
+        if hybrid.q > 0.0 {
+            <Self as Strategy<Blackboard>>::contribute(self, &mut hybrid.blackboard);
+        }
+        <Self as Strategy<Imitation>>::contribute(self, &mut hybrid.imitation);
+    }
+    fn is_solved(&self) -> bool {
+        self.cost == 0 && Agent::is_solved(self)
+    }
+    fn assignment(&self) -> Solution {
+        self.solution()
     }
 }
 
-pub fn computational_cost(m: u32, t: f64) -> f64 {
-    (m as f64) * t / 3628800.0f64
+// normalized by the search space of the problem, so costs are comparable across puzzles
+pub fn computational_cost(problem: &Problem, m: u32, t: f64) -> f64 {
+    (m as f64) * t / problem.search_space()
 }
 
-// #[cfg(test)]
-// mod tests {
-//     use std::vec;
-
-//     use rand::seq::SliceRandom;
-
-//     use crate::{Agent, Hint, LetterAssignment, Problem};
-
-//     #[test]
-//     fn problem() {
-//         let problem = Problem::default();
-//         assert!(problem.first == "donald");
-//         assert!(problem.second == "gerald");
-//         assert!(problem.result == "robert");
-//     }
-
-//     #[test]
-//     fn agent() {
-//         let problem = Problem::default();
-//         let mut agent = Agent::new(&problem);
-
-//         assert!(agent.assignment.len() == 10);
-//         let assignment = agent.assignment.clone();
-//         agent.assign_random();
-//         assert!(agent.assignment != assignment);
-
-//         agent.swap_letter_assignment(&LetterAssignment {
-//             letter: 'd',
-//             value: 4,
-//         });
-//         agent.swap_letter_assignment(&LetterAssignment {
-//             letter: 'o',
-//             value: 5,
-//         });
-
-//         assert!(*agent.assignment.get_by_left(&'d').unwrap() == 4);
-//         assert!(*agent.assignment.get_by_left(&'o').unwrap() == 5);
-
-//         let assignment = agent.assignment.clone();
-//         agent.elementary_move();
-//         assert!(agent.assignment != assignment);
-
-//         assert!(!agent.is_solved());
-
-//         let hint = Hint::Two(
-//             LetterAssignment {
-//                 letter: 'd',
-//                 value: 5,
-//             },
-//             LetterAssignment {
-//                 letter: 't',
-//                 value: 0,
-//             },
-//         );
-
-//         agent.assign_random();
-
-//         let mut blackboard = vec![hint.clone()];
-
-//         assert!(blackboard.len() != 0);
-//         assert!(blackboard.choose(&mut rand::thread_rng()).unwrap().clone() == hint);
-//         assert!(agent.hints.contains(&hint) == false);
-
-//         agent.make_move(&mut blackboard);
-//         agent.find_hints();
-//         assert!(agent.hints.contains(&hint));
-
-//         agent.assign_random();
-//         assert!(!agent.is_solved());
-//         agent.assimilate_hint(&Hint::Three(
-//             LetterAssignment {
-//                 letter: 'a',
-//                 value: 4,
-//             },
-//             LetterAssignment {
-//                 letter: 'b',
-//                 value: 3,
-//             },
-//             LetterAssignment {
-//                 letter: 'd',
-//                 value: 5,
-//             },
-//         ));
-//         agent.assimilate_hint(&Hint::Three(
-//             LetterAssignment {
-//                 letter: 'e',
-//                 value: 9,
-//             },
-//             LetterAssignment {
-//                 letter: 'g',
-//                 value: 1,
-//             },
-//             LetterAssignment {
-//                 letter: 'l',
-//                 value: 8,
-//             },
-//         ));
-//         agent.assimilate_hint(&Hint::Three(
-//             LetterAssignment {
-//                 letter: 'n',
-//                 value: 6,
-//             },
-//             LetterAssignment {
-//                 letter: 'o',
-//                 value: 2,
-//             },
-//             LetterAssignment {
-//                 letter: 'r',
-//                 value: 7,
-//             },
-//         ));
-//         agent.assimilate_hint(&Hint::One(LetterAssignment {
-//             letter: 't',
-//             value: 0,
-//         }));
-//         assert!(agent.is_solved());
-//     }
-// }
+// agent with a random assignment, as every model starts them
+pub(crate) fn random_agent(
+    problem: &Problem,
+    options: AgentOptions,
+    compute_phi: bool,
+) -> Agent<'_> {
+    let mut agent = Agent::new(problem, options, compute_phi);
+    agent.assign_random();
+    agent
+}
+
+// runs every job n times in parallel, the parallel runners of all models share it
+pub(crate) fn sweep<J: Clone + Send, R: Send>(
+    jobs: Vec<J>,
+    n: u32,
+    show_progress: bool,
+    run: impl Fn(J) -> Option<R> + Sync,
+) -> Vec<Option<R>> {
+    let results: Mutex<Vec<Option<R>>> = Mutex::new(vec![]);
+    let jobs_len = jobs.len() * n as usize;
+
+    if show_progress {
+        let pb = Mutex::new(ProgressBar::new(jobs_len as u64));
+
+        iproduct!(jobs, 0..n)
+            .collect_vec()
+            .into_par_iter()
+            .for_each(|(job, _)| {
+                let r = run(job);
+                pb.lock().unwrap().inc(1);
+                results.lock().unwrap().push(r);
+            });
+    } else {
+        iproduct!(jobs, 0..n)
+            .collect_vec()
+            .into_par_iter()
+            .for_each(|(job, _)| {
+                let r = run(job);
+                results.lock().unwrap().push(r);
+            });
+    }
+    results.into_inner().expect("Error unwrapping results")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options::{ReadPolicy, Replacement};
+    use itertools::Itertools;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    fn value(word: &str, solution: &Solution, base: u32) -> u128 {
+        word.chars()
+            .fold(0, |v, c| v * base as u128 + solution[&c] as u128)
+    }
+
+    fn holds(problem: &Problem, solution: &Solution) -> bool {
+        let base = problem.base;
+        // a single letter word has a leading letter too, as in Problem::is_leading
+        let leading = problem
+            .words()
+            .any(|w| solution[&w.chars().next().unwrap()] == 0);
+        if leading && problem.leading_zeros != LeadingZeros::Allow {
+            return false;
+        }
+        let mut operands = problem.operands.iter().map(|w| value(w, solution, base));
+        let first = operands.next().unwrap();
+        let left = match problem.operator {
+            Operator::Add => operands.try_fold(first, |v, o| v.checked_add(o)),
+            Operator::Subtract => operands.try_fold(first, |v, o| v.checked_sub(o)),
+            Operator::Multiply => operands.try_fold(first, |v, o| v.checked_mul(o)),
+        };
+        left == Some(value(&problem.result, solution, base))
+    }
+
+    // every assignment of distinct digits to the letters, checked one by one
+    fn brute_force(problem: &Problem) -> usize {
+        let letters: Vec<char> = problem.letters.iter().copied().collect();
+        (0..problem.base)
+            .permutations(letters.len())
+            .filter(|digits| {
+                let solution: Solution = letters.iter().copied().zip(digits.clone()).collect();
+                holds(problem, &solution)
+            })
+            .count()
+    }
+
+    fn parse(equation: &str) -> Problem {
+        Problem::parse(equation, 10).unwrap()
+    }
+
+    #[test]
+    fn known_solution_counts() {
+        assert_eq!(parse("SEND + MORE = MONEY").solution_count(), 1);
+        assert_eq!(parse("DONALD + GERALD = ROBERT").solution_count(), 1);
+        assert_eq!(parse("MONEY - MORE = SEND").solution_count(), 1);
+        assert_eq!(
+            parse("SEND + MORE = MONEY")
+                .with_leading_zeros(LeadingZeros::Allow)
+                .solution_count(),
+            25
+        );
+    }
+
+    #[test]
+    fn solutions_hold() {
+        for equation in [
+            "SEND + MORE = MONEY",
+            "DONALD + GERALD = ROBERT",
+            "MONEY - MORE = SEND",
+            "ABC * DE = FGHIJ",
+        ] {
+            let problem = parse(equation);
+            for solution in problem.solutions() {
+                assert!(holds(&problem, solution), "{} {:?}", equation, solution);
+            }
+        }
+    }
+
+    #[test]
+    fn solver_matches_brute_force() {
+        for equation in [
+            "TO + GO = OUT",
+            "AB + BA = CC",
+            "ABC - DE = FG",
+            "AB * C = DEF",
+            "A + BC = BD",
+        ] {
+            for leading_zeros in [LeadingZeros::Allow, LeadingZeros::Penalize] {
+                let problem = parse(equation).with_leading_zeros(leading_zeros);
+                assert_eq!(
+                    problem.solution_count(),
+                    brute_force(&problem),
+                    "{} with {}",
+                    equation,
+                    leading_zeros
+                );
+            }
+        }
+        let problem = Problem::parse("AB + BA = CDA", 7).unwrap();
+        assert_eq!(problem.solution_count(), brute_force(&problem));
+    }
+
+    #[test]
+    fn parse_errors() {
+        let error = |equation: &str, base| Problem::parse(equation, base).unwrap_err();
+        assert_eq!(error("SEND + MORE", 10), ProblemError::MissingEquals);
+        assert_eq!(error("A + B = C = D", 10), ProblemError::MultipleEquals);
+        assert_eq!(
+            error("A + B - C = D", 10),
+            ProblemError::MixedOperators(Operator::Add, Operator::Subtract)
+        );
+        assert_eq!(error("SEND = MONEY", 10), ProblemError::TooFewOperands);
+        assert_eq!(error("SEND + = MONEY", 10), ProblemError::EmptyWord);
+        assert_eq!(error("SEND + MORE = ", 10), ProblemError::EmptyWord);
+        assert_eq!(
+            error("SEND1 + MORE = MONEY", 10),
+            ProblemError::InvalidCharacter('1')
+        );
+        assert_eq!(
+            error("ABC + DEF = GHIJK", 10),
+            ProblemError::TooManyLetters {
+                letters: 11,
+                base: 10
+            }
+        );
+        assert_eq!(error("A + B = C", 1), ProblemError::InvalidBase(1));
+        assert_eq!(
+            "forbid".parse::<LeadingZeros>().unwrap_err(),
+            UnknownLeadingZeros("forbid".to_string())
+        );
+    }
+
+    #[test]
+    fn false_hints_satisfy_a_column() {
+        let problem = parse("SEND + MORE = MONEY");
+        let agent = Agent::new(&problem, AgentOptions::default(), false);
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..100 {
+            let hint = agent.forge(true, &mut rng).unwrap();
+            let digit = |c: char| {
+                hint.assignments()
+                    .iter()
+                    .find(|a| a.letter == c)
+                    .map(|a| a.value)
+            };
+            assert!(!hint.is_correct(&problem));
+            assert!((0..problem.width()).any(|i| {
+                let letters = problem.column_letters(i);
+                letters.iter().unique().count() == hint.assignments().len()
+                    && letters.iter().all(|c| digit(*c).is_some())
+                    && problem.is_column_consistent(i, |c| digit(c).unwrap())
+            }));
+        }
+    }
+
+    #[test]
+    fn boards_count_bad_and_forged_hints() {
+        let problem = parse("SEND + MORE = MONEY");
+        // hints on a board too large to drop any, with every agent an adversary of the mode
+        let posts = |mode: Option<AdversaryMode>| {
+            let mut board = Blackboard::new(1000, Replacement::default(), ReadPolicy::default());
+            let mut agents: Vec<_> = (0..5)
+                .map(|_| {
+                    let agent = random_agent(&problem, AgentOptions::default(), false);
+                    match mode {
+                        Some(mode) => agent.with_adversary(Adversaries {
+                            fraction: 1.0,
+                            mode,
+                            e: 1.0,
+                        }),
+                        None => agent,
+                    }
+                })
+                .collect();
+            for _ in 0..50 {
+                for (i, a) in agents.iter_mut().enumerate() {
+                    a.observe(&board);
+                    <_ as Strategy<Blackboard>>::act(a);
+                    board.begin_turn(i);
+                    a.contribute(&mut board);
+                }
+            }
+            let bad = board.hints().iter().filter(|h| !h.is_correct(&problem));
+            assert!(!board.hints().is_empty());
+            assert_eq!(board.bad_posts() as usize, bad.count());
+            let n = board.hints().len();
+            (n, board.bad_posts() as usize, board.forged_posts() as usize)
+        };
+
+        assert_eq!(posts(None).2, 0);
+        // a noisy agent may move a wrong digit of its hint to the right one
+        for mode in [AdversaryMode::Random, AdversaryMode::Noisy] {
+            let (n, _, forged) = posts(Some(mode));
+            assert_eq!(forged, n, "{mode}");
+        }
+        let (n, bad, forged) = posts(Some(AdversaryMode::False));
+        assert_eq!((bad, forged), (n, n));
+    }
+
+    #[test]
+    fn moves_keep_assignments_injective() {
+        for equation in ["A + B = C", "TO + GO = OUT"] {
+            let problem = parse(equation);
+            for move_operator in [
+                MoveOperator::Reassign,
+                MoveOperator::Swap,
+                MoveOperator::Rotate,
+                MoveOperator::ColumnScramble,
+            ] {
+                let options = AgentOptions {
+                    move_operator,
+                    ..AgentOptions::default()
+                };
+                let mut agent = random_agent(&problem, options, false);
+                for _ in 0..1000 {
+                    let before = agent.solution();
+                    agent.elementary_move();
+                    let after = agent.solution();
+                    assert_eq!(after.len(), problem.letters.len());
+                    assert!(after.values().all(|d| *d < problem.base));
+                    // letters outside the move keep their digits
+                    let moved: Vec<char> = problem
+                        .letters
+                        .iter()
+                        .copied()
+                        .filter(|l| before[l] != after[l])
+                        .collect();
+                    let in_column = |i| {
+                        let letters = problem.letters_in_column(i);
+                        moved.iter().all(|l| letters.contains(l))
+                    };
+                    match move_operator {
+                        MoveOperator::Rotate => assert!(moved.len() <= options.rotation_size),
+                        MoveOperator::ColumnScramble => {
+                            assert!((0..problem.width()).any(in_column))
+                        }
+                        _ => assert!(moved.len() <= 2, "{move_operator} moved {moved:?}"),
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn costs_are_normalized_by_the_search_space() {
+        let problem = parse("DONALD + GERALD = ROBERT");
+        assert_eq!(problem.search_space(), 3628800.0);
+        assert_eq!(computational_cost(&problem, 8, 453600.0), 1.0);
+        // 10 * 9 * 8 injective assignments of three letters
+        let problem = parse("A + B = C");
+        assert_eq!(problem.search_space(), 720.0);
+        assert_eq!(computational_cost(&problem, 8, 90.0), 1.0);
+        let problem = Problem::parse("AB + BA = CDA", 7).unwrap();
+        assert_eq!(problem.search_space(), 840.0);
+    }
+
+    // agent holding the only solution of the problem
+    fn solved_agent(problem: &Problem) -> Agent<'_> {
+        let mut agent = Agent::new(problem, AgentOptions::default(), false);
+        let solution = &problem.solutions()[0];
+        agent.assimilate_hint(&Hint::new(solution.iter().map(|(letter, value)| {
+            LetterAssignment {
+                letter: *letter,
+                value: *value,
+            }
+        })));
+        agent
+    }
+
+    // the hint of the letters with their digits in the solution
+    fn hint(problem: &Problem, letters: &str) -> Hint {
+        let solution = &problem.solutions()[0];
+        Hint::new(letters.chars().map(|letter| LetterAssignment {
+            letter,
+            value: solution[&letter],
+        }))
+    }
+
+    #[test]
+    fn hints_align_to_the_right() {
+        let problem = parse("SEND + MORE = MONEY");
+        let mut agent = solved_agent(&problem);
+        agent.find_hints();
+        assert_eq!(
+            agent.hints,
+            ["dey", "enr", "noe", "smo", "m"].map(|letters| hint(&problem, letters))
+        );
+        // a single letter addend only has a digit in the units column
+        let problem = parse("A + BC = BD");
+        let mut agent = solved_agent(&problem);
+        agent.find_hints();
+        assert_eq!(
+            agent.hints,
+            ["acd", "b"].map(|letters| hint(&problem, letters))
+        );
+    }
+
+    #[test]
+    fn multi_addend_problems_solve_column_by_column() {
+        let problem = parse("SIX + SEVEN + SEVEN = TWENTY");
+        assert_eq!(problem.solution_count(), 1);
+        let mut agent = solved_agent(&problem);
+        assert!(agent.is_solved());
+        for kind in [
+            CostKind::AbsoluteDifference,
+            CostKind::ColumnMismatch,
+            CostKind::DigitHamming,
+        ] {
+            agent.compute_cost(kind.function());
+            assert_eq!(agent.cost, 0, "{kind}");
+        }
+        // three addends can carry 2 into the next column
+        agent.find_hints();
+        assert_eq!(
+            agent.hints,
+            ["nxy", "eit", "nsv", "e", "sw", "t"].map(|letters| hint(&problem, letters))
+        );
+
+        agent.swap_letter_assignment(&LetterAssignment {
+            letter: 'x',
+            value: (problem.solutions()[0][&'x'] + 1) % 10,
+        });
+        assert!(!agent.is_solved());
+    }
+
+    #[test]
+    fn greedy_agents_never_get_worse() {
+        let problem = parse("SEND + MORE = MONEY");
+        let function = CostKind::default().function();
+        for move_operator in [MoveOperator::Reassign, MoveOperator::ColumnScramble] {
+            let options = AgentOptions {
+                move_operator,
+                acceptance: Acceptance::Greedy,
+                ..AgentOptions::default()
+            };
+            let mut agent = random_agent(&problem, options, false);
+            agent.compute_cost(function);
+            for _ in 0..1000 {
+                let cost = agent.cost;
+                agent.elementary_move();
+                assert!(agent.cost <= cost);
+                // a rejected move restores the assignment the cost belongs to
+                let kept = agent.cost;
+                agent.compute_cost(function);
+                assert_eq!(agent.cost, kept);
+            }
+        }
+    }
+}
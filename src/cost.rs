@@ -0,0 +1,165 @@
+use crate::common::Agent;
+use serde::Deserialize;
+use std::{fmt, str::FromStr};
+
+// Fitness landscape the imitative model searches. Every solution costs 0, but not everything
+// that costs 0 has to be a solution, so the model still checks the equation itself.
+// Assignments with a leading zero are penalized by the agent before any of these is asked.
+// The functions read the agent's internals, so they are chosen through CostKind only.
+pub(crate) trait CostFunction: Send + Sync {
+    fn cost(&self, agent: &Agent) -> u128;
+}
+
+// |result - (first + second + ...)|, saturating beyond u128::MAX
+pub struct AbsoluteDifference;
+
+// number of columns that can not be satisfied by any carry, the columns that give no hint
+pub struct ColumnMismatch;
+
+// number of digits in which the evaluated left hand side differs from the result, carries of
+// a wrong digit spread into the columns above it
+pub struct DigitHamming;
+
+impl CostFunction for AbsoluteDifference {
+    fn cost(&self, agent: &Agent) -> u128 {
+        match agent.sides() {
+            Some((left, right)) => left.abs_diff(right),
+            None => {
+                let (left, right) = agent.wide_sides();
+                left.abs_diff(&right).to_u128().unwrap_or(u128::MAX)
+            }
+        }
+    }
+}
+
+impl CostFunction for ColumnMismatch {
+    fn cost(&self, agent: &Agent) -> u128 {
+        let problem = agent.problem;
+        (0..problem.width())
+            .filter(|i| !problem.is_column_consistent(*i, |c| agent.digit(c)))
+            .count() as u128
+    }
+}
+
+impl CostFunction for DigitHamming {
+    fn cost(&self, agent: &Agent) -> u128 {
+        match agent.sides() {
+            Some((mut left, mut right)) => {
+                let base = agent.problem.base() as u128;
+                let mut distance = 0;
+                while left > 0 || right > 0 {
+                    if left % base != right % base {
+                        distance += 1;
+                    }
+                    left /= base;
+                    right /= base;
+                }
+                distance
+            }
+            None => {
+                let (left, right) = agent.wide_sides();
+                (0..left.len().max(right.len()))
+                    .filter(|i| left.digit(*i) != right.digit(*i))
+                    .count() as u128
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CostKind {
+    #[default]
+    AbsoluteDifference,
+    ColumnMismatch,
+    DigitHamming,
+}
+
+impl CostKind {
+    pub(crate) fn function(&self) -> &'static dyn CostFunction {
+        match self {
+            CostKind::AbsoluteDifference => &AbsoluteDifference,
+            CostKind::ColumnMismatch => &ColumnMismatch,
+            CostKind::DigitHamming => &DigitHamming,
+        }
+    }
+}
+
+impl fmt::Display for CostKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CostKind::AbsoluteDifference => write!(f, "absolute_difference"),
+            CostKind::ColumnMismatch => write!(f, "column_mismatch"),
+            CostKind::DigitHamming => write!(f, "digit_hamming"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownCostFunction(String);
+
+impl std::error::Error for UnknownCostFunction {}
+impl fmt::Display for UnknownCostFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "error: unknown cost function '{}', expected absolute_difference, column_mismatch or digit_hamming",
+            self.0
+        )
+    }
+}
+
+impl FromStr for CostKind {
+    type Err = UnknownCostFunction;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "absolute_difference" => Ok(CostKind::AbsoluteDifference),
+            "column_mismatch" => Ok(CostKind::ColumnMismatch),
+            "digit_hamming" => Ok(CostKind::DigitHamming),
+            _ => Err(UnknownCostFunction(s.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::{Hint, LetterAssignment, Problem};
+    use crate::options::AgentOptions;
+
+    const KINDS: [CostKind; 3] = [
+        CostKind::AbsoluteDifference,
+        CostKind::ColumnMismatch,
+        CostKind::DigitHamming,
+    ];
+
+    // cost of the assignment given as the digits of the letters in "sendmory"
+    fn cost(problem: &Problem, kind: CostKind, digits: [u32; 8]) -> u128 {
+        let mut agent = Agent::new(problem, AgentOptions::default(), false);
+        agent.assimilate_hint(&Hint::new(
+            "sendmory"
+                .chars()
+                .zip(digits)
+                .map(|(letter, value)| LetterAssignment { letter, value }),
+        ));
+        agent.compute_cost(kind.function());
+        agent.cost
+    }
+
+    #[test]
+    fn solution_costs_nothing_and_near_misses_cost_less() {
+        let problem = Problem::parse("SEND + MORE = MONEY", 10).unwrap();
+        let solution = [9, 5, 6, 7, 1, 0, 8, 2];
+        // N and R swapped, only the tens column is off
+        let near = [9, 5, 8, 7, 1, 0, 6, 2];
+        // every digit moved
+        let far = [4, 0, 1, 2, 6, 5, 3, 7];
+        for kind in KINDS {
+            let near = cost(&problem, kind, near);
+            assert_eq!(cost(&problem, kind, solution), 0, "{kind}");
+            assert!(near > 0, "{kind}");
+            assert!(near < cost(&problem, kind, far), "{kind}");
+        }
+    }
+}
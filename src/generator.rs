@@ -0,0 +1,373 @@
+use crate::common::Problem;
+use config::Config;
+use rand::{seq::SliceRandom, Rng};
+use serde::Deserialize;
+use std::{
+    cmp::Ordering, collections::HashSet, env, fmt, fs, fs::File, io::prelude::*, io::BufWriter,
+};
+
+#[derive(Debug)]
+pub struct GeneratorResult {
+    pub equation: String,
+    pub base: u32,
+    pub letters: usize,
+    pub search_space: f64,
+    pub columns: usize,
+    pub hint_columns: usize,
+}
+
+impl GeneratorResult {
+    fn new(equation: String, problem: &Problem) -> Self {
+        Self {
+            equation,
+            base: problem.base(),
+            letters: problem.letter_count(),
+            search_space: problem.search_space(),
+            columns: problem.width(),
+            hint_columns: problem.hint_columns(),
+        }
+    }
+}
+
+impl fmt::Display for GeneratorResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "{},{},{},{},{},{}",
+            self.equation,
+            self.base,
+            self.letters,
+            self.search_space,
+            self.columns,
+            self.hint_columns
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GeneratorError {
+    InvalidSizes(String),
+    // no attempt found a problem with exactly one solution
+    Exhausted,
+}
+
+impl std::error::Error for GeneratorError {}
+impl fmt::Display for GeneratorError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GeneratorError::InvalidSizes(s) => write!(f, "error: {}", s),
+            GeneratorError::Exhausted => {
+                write!(
+                    f,
+                    "error: no attempt found a problem with exactly one solution"
+                )
+            }
+        }
+    }
+}
+
+// checks that an addition with these sizes can exist and that there are names for its letters
+pub fn validate_sizes(letters: usize, word_lengths: &[usize], base: u32) -> Result<(), String> {
+    if base < 2 {
+        return Err("base must be >= 2".to_string());
+    }
+    if word_lengths.len() < 3 || word_lengths.contains(&0) {
+        return Err(
+            "word_lengths needs at least two addends and a sum, all longer than 0".to_string(),
+        );
+    }
+    if letters == 0 || letters > base as usize || letters > 26 {
+        return Err("letters must be > 0 and at most base and 26".to_string());
+    }
+    let (sum, addends) = word_lengths.split_last().unwrap();
+    let largest: f64 = addends
+        .iter()
+        .map(|len| (base as f64).powi(*len as i32) - 1.0)
+        .sum();
+    if sum < addends.iter().max().unwrap() || largest < (base as f64).powi(*sum as i32 - 1) {
+        return Err("no sum of the addends has the length of the sum".to_string());
+    }
+    Ok(())
+}
+
+// random addition with exactly one solution, word_lengths lists the addends followed by the sum
+// words are picked from the word list if there is one, otherwise the letters are random
+pub fn generate(
+    letters: usize,
+    word_lengths: &[usize],
+    base: u32,
+    words: Option<&[String]>,
+    max_attempts: u32,
+) -> Result<GeneratorResult, GeneratorError> {
+    let mut rng = rand::thread_rng();
+    generate_with(&mut rng, letters, word_lengths, base, words, max_attempts)
+}
+
+// same as generate with the random numbers of rng
+pub fn generate_with(
+    rng: &mut impl Rng,
+    letters: usize,
+    word_lengths: &[usize],
+    base: u32,
+    words: Option<&[String]>,
+    max_attempts: u32,
+) -> Result<GeneratorResult, GeneratorError> {
+    validate_sizes(letters, word_lengths, base).map_err(GeneratorError::InvalidSizes)?;
+    // candidates for every word of the equation
+    let candidates: Option<Vec<Vec<&String>>> = words.map(|words| {
+        word_lengths
+            .iter()
+            .map(|len| words.iter().filter(|w| w.len() == *len).collect())
+            .collect()
+    });
+    for _ in 0..max_attempts {
+        let equation = match &candidates {
+            Some(candidates) => from_words(rng, letters, candidates),
+            None => from_digits(rng, letters, word_lengths, base),
+        };
+        let Some(equation) = equation else {
+            continue;
+        };
+        if let Ok(problem) = Problem::parse(&equation, base) {
+            if problem.solution_count() == 1 {
+                return Ok(GeneratorResult::new(equation, &problem));
+            }
+        }
+    }
+    Err(GeneratorError::Exhausted)
+}
+
+fn equation(words: &[String]) -> String {
+    let (sum, addends) = words.split_last().unwrap();
+    format!("{} = {}", addends.join(" + "), sum).to_uppercase()
+}
+
+// picks a word of every length, only the letter count is checked here
+fn from_words(rng: &mut impl Rng, letters: usize, candidates: &[Vec<&String>]) -> Option<String> {
+    let picked = candidates
+        .iter()
+        .map(|c| c.choose(rng).map(|w| w.to_string()))
+        .collect::<Option<Vec<String>>>()?;
+
+    let distinct: HashSet<char> = picked.iter().flat_map(|w| w.chars()).collect();
+    (distinct.len() == letters).then(|| equation(&picked))
+}
+
+// draws random addends from a random set of digits and keeps the sum if it only uses those
+// digits, the digits are then replaced by random letters
+fn from_digits(
+    rng: &mut impl Rng,
+    letters: usize,
+    word_lengths: &[usize],
+    base: u32,
+) -> Option<String> {
+    let all: Vec<u32> = (0..base).collect();
+    let digits: Vec<u32> = all.choose_multiple(rng, letters).copied().collect();
+    let leading: Vec<u32> = digits.iter().filter(|d| **d != 0).copied().collect();
+    let (sum_length, addend_lengths) = word_lengths.split_last()?;
+
+    // least significant digit first
+    let addends: Vec<Vec<u32>> = addend_lengths
+        .iter()
+        .map(|len| {
+            (0..*len)
+                .map(|i| {
+                    if i + 1 == *len {
+                        leading.choose(rng).copied()
+                    } else {
+                        digits.choose(rng).copied()
+                    }
+                })
+                .collect::<Option<Vec<u32>>>()
+        })
+        .collect::<Option<_>>()?;
+
+    let width = addend_lengths.iter().max().copied().unwrap_or(0);
+    let mut sum = Vec::new();
+    let mut carry = 0;
+    for i in 0..width {
+        let total = carry + addends.iter().filter_map(|a| a.get(i)).sum::<u32>();
+        sum.push(total % base);
+        carry = total / base;
+    }
+    while carry > 0 {
+        sum.push(carry % base);
+        carry /= base;
+    }
+    if sum.len() != *sum_length {
+        return None;
+    }
+
+    // every chosen digit has to appear, and no other
+    let used: HashSet<u32> = addends.iter().chain([&sum]).flatten().copied().collect();
+    if used.len() != letters || !used.iter().all(|d| digits.contains(d)) {
+        return None;
+    }
+
+    let alphabet: Vec<char> = ('a'..='z').collect();
+    let names: Vec<char> = alphabet.choose_multiple(rng, letters).copied().collect();
+    let name = |d: &u32| names[digits.iter().position(|x| x == d).unwrap()];
+    let words: Vec<String> = addends
+        .iter()
+        .chain([&sum])
+        .map(|w| w.iter().rev().map(name).collect())
+        .collect();
+    Some(equation(&words))
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct GeneratorConfigData {
+    pub letters: usize,
+    pub word_lengths: Vec<usize>,
+    pub base: u32,
+    pub word_list: Option<String>,
+    pub max_attempts: u32,
+    pub n_problems: u32,
+    pub output: String,
+    pub use_threads: bool,
+    pub flush_frequency: u32,
+}
+
+impl GeneratorConfigData {
+    // lowercase words of the word list, anything that is not a single word is skipped
+    pub fn load_words(&self) -> Result<Option<Vec<String>>, std::io::Error> {
+        match &self.word_list {
+            Some(path) => Ok(Some(
+                fs::read_to_string(path)?
+                    .lines()
+                    .map(|w| w.trim().to_lowercase())
+                    .filter(|w| !w.is_empty() && w.chars().all(|c| c.is_ascii_alphabetic()))
+                    .collect(),
+            )),
+            None => Ok(None),
+        }
+    }
+}
+
+impl GeneratorConfigData {
+    pub fn log(&self) {
+        println!("info: starting batch",);
+        println!("    - letters:         {}", self.letters);
+        println!("    - word_lengths:    {:?}", self.word_lengths);
+        println!("    - base:            {}", self.base);
+        println!(
+            "    - word_list:       {}",
+            self.word_list.as_deref().unwrap_or("random letters")
+        );
+        println!("    - max_attempts:    {}", self.max_attempts);
+        println!("    - n_problems:      {}", self.n_problems);
+        println!("    - output:          {}", self.output);
+        println!("    - use_threads:     {}", self.use_threads);
+        println!("    - flush_frequency: {}", self.flush_frequency);
+    }
+}
+
+pub fn generator_get_args() -> Result<GeneratorConfigData, Box<dyn std::error::Error>> {
+    let args: Vec<String> = env::args().collect();
+    let config = match args.len().cmp(&3) {
+        Ordering::Less => {
+            println!(
+                "info: no config file provided\ninfo: checking for config with name 'generator_default'"
+            );
+            Config::builder()
+                .add_source(config::File::with_name("generator_default"))
+                .set_default("flush_frequency", 100)?
+                .set_default("use_threads", true)?
+                .set_default("base", 10)?
+                .set_default("max_attempts", 100000)?
+                .build()
+        }
+        Ordering::Greater => Err(config::ConfigError::Message(
+            "provided more arguments than allowed".to_string(),
+        )),
+        _ => {
+            let name = args.get(2).unwrap();
+            println!("info: checking for config with name '{}'", name);
+            Config::builder()
+                .add_source(config::File::with_name(name))
+                .set_default("flush_frequency", 100)?
+                .set_default("use_threads", true)?
+                .set_default("base", 10)?
+                .set_default("max_attempts", 100000)?
+                .build()
+        }
+    }?;
+    println!("info: loaded config file");
+    let cfg = config.try_deserialize::<GeneratorConfigData>()?;
+    if cfg.n_problems == 0 {
+        Err(config::ConfigError::Message(
+            "n_problems must be > 0".to_string(),
+        ))?;
+    }
+    validate_sizes(cfg.letters, &cfg.word_lengths, cfg.base)
+        .map_err(config::ConfigError::Message)?;
+
+    Ok(cfg)
+}
+
+pub struct GeneratorResultsWriter {
+    file: BufWriter<File>,
+    counter: u32,
+    flush_frequency: u32,
+}
+
+impl GeneratorResultsWriter {
+    pub fn new(filename: String, flush_frequency: u32) -> Self {
+        Self {
+            file: BufWriter::new(File::create(filename).unwrap()),
+            counter: 0,
+            flush_frequency,
+        }
+    }
+    pub fn write(&mut self, result: Option<GeneratorResult>) -> bool {
+        match result {
+            Some(r) => {
+                let _ = self.file.write(r.to_string().as_bytes());
+                if self.counter == self.flush_frequency {
+                    self.file.flush().unwrap();
+                    self.counter = 0;
+                }
+                self.counter += 1;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn generated_problems_have_one_solution() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for (letters, word_lengths, base) in [
+            (7, vec![3, 4, 5], 10),
+            (6, vec![3, 3, 3, 4], 7),
+            (6, vec![3, 3, 4], 8),
+        ] {
+            for _ in 0..3 {
+                let result =
+                    generate_with(&mut rng, letters, &word_lengths, base, None, 100_000).unwrap();
+                let problem = Problem::parse(&result.equation, base).unwrap();
+                assert_eq!(problem.solutions().len(), 1, "{}", result.equation);
+                assert_eq!(result.letters, letters);
+            }
+        }
+    }
+
+    #[test]
+    fn generate_errors() {
+        let mut rng = StdRng::seed_from_u64(0);
+        assert!(matches!(
+            generate_with(&mut rng, 11, &[4, 4, 5], 10, None, 10),
+            Err(GeneratorError::InvalidSizes(_))
+        ));
+        assert_eq!(
+            generate_with(&mut rng, 8, &[4, 4, 5], 10, None, 0).unwrap_err(),
+            GeneratorError::Exhausted
+        );
+    }
+}
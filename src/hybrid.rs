@@ -0,0 +1,372 @@
+use crate::common::{computational_cost, random_agent, sweep, Problem, ProblemConfigData};
+use crate::cost::CostKind;
+use crate::imitative::{pick_model, ImitationSettings};
+use crate::options::{
+    AgentOptions, ImitationIntensity, ImitationTarget, Mix, ReadPolicy, Replacement,
+};
+use crate::strategy::{Hybrid, Strategy};
+use config::Config;
+use itertools::iproduct;
+use rand::Rng;
+use serde::Deserialize;
+use std::{cmp::Ordering, env, fmt, fs::File, io::prelude::*, io::BufWriter, ops::Range};
+
+#[derive(Debug)]
+pub struct HybridResult {
+    pub m: u32,
+    pub b: u32,
+    pub t_star: f64,
+    pub c: f64,
+    pub mix: Mix,
+    // agents that read the board, that imitate and that do neither, as Mix::counts gives them
+    pub users: u32,
+    pub imitators: u32,
+    pub loners: u32,
+    pub settings: ImitationSettings,
+    pub replacement: Replacement,
+    pub read_policy: ReadPolicy,
+}
+
+impl fmt::Display for HybridResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (p, q) = self.mix.probabilities();
+        writeln!(
+            f,
+            "{},{},{},{},{},{},{},{},{},{},{},{},{}",
+            self.m,
+            self.b,
+            self.t_star,
+            self.c,
+            self.mix.name(),
+            self.users,
+            self.imitators,
+            self.loners,
+            p,
+            q,
+            self.settings,
+            self.replacement,
+            self.read_policy
+        )
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn hybrid(
+    problem: &Problem,
+    m: u32,
+    b: u32,
+    mix: &Mix,
+    max_c: f64,
+    cost_function: CostKind,
+    intensity: ImitationIntensity,
+    target: ImitationTarget,
+    replacement: Replacement,
+    read_policy: ReadPolicy,
+    options: AgentOptions,
+) -> Option<HybridResult> {
+    hybrid_with(
+        problem,
+        m,
+        b,
+        mix,
+        intensity,
+        target,
+        replacement,
+        read_policy,
+        max_c,
+        || random_agent(problem, options, false).with_cost_function(cost_function),
+    )
+    .map(|r| HybridResult {
+        settings: r.settings.with_cost_function(cost_function),
+        ..r
+    })
+}
+
+// hybrid model with the agents spawn creates, the agents share one blackboard of size b and
+// imitate the model the target rule picks
+#[allow(clippy::too_many_arguments)]
+pub fn hybrid_with<S: Strategy<Hybrid>>(
+    problem: &Problem,
+    m: u32,
+    b: u32,
+    mix: &Mix,
+    intensity: ImitationIntensity,
+    target: ImitationTarget,
+    replacement: Replacement,
+    read_policy: ReadPolicy,
+    max_c: f64,
+    spawn: impl Fn() -> S,
+) -> Option<HybridResult> {
+    let delta = 1.0 / (m as f64);
+    let roles = mix.roles(m as usize);
+    let mut hybrid = Hybrid::new(b as usize, replacement, read_policy, intensity);
+    let mut agents: Vec<S> = (0..m).map(|_| spawn()).collect();
+    let mut best_agent: usize = 0;
+    let mut best_cost: u128 = u128::MAX;
+    let mut costs: Vec<u128> = vec![u128::MAX; agents.len()];
+
+    let mut t = 1.0;
+
+    for (i, a) in agents.iter_mut().enumerate() {
+        hybrid.begin_turn(i, roles[i]);
+        a.contribute(&mut hybrid);
+        costs[i] = hybrid.imitation.reported_cost;
+        if hybrid.imitation.reported_cost < best_cost {
+            best_agent = i;
+            best_cost = hybrid.imitation.reported_cost;
+        }
+    }
+
+    while computational_cost(problem, m, t) < max_c {
+        let i: usize = rand::thread_rng().gen_range(0..agents.len());
+        hybrid.imitation.model =
+            pick_model(target, i, best_agent, &costs, None).map(|j| agents[j].assignment());
+        hybrid.begin_turn(i, roles[i]);
+        let a = agents.get_mut(i).unwrap();
+
+        a.observe(&hybrid);
+        a.act();
+        t += delta;
+        a.contribute(&mut hybrid);
+        costs[i] = hybrid.imitation.reported_cost;
+        if hybrid.imitation.reported_cost < best_cost {
+            best_agent = i;
+            best_cost = hybrid.imitation.reported_cost;
+        }
+        if a.is_solved() {
+            let (users, imitators, loners) = mix.counts(m as usize);
+            return Some(HybridResult {
+                m,
+                b,
+                t_star: t,
+                c: computational_cost(problem, m, t),
+                mix: *mix,
+                users: users as u32,
+                imitators: imitators as u32,
+                loners: loners as u32,
+                settings: ImitationSettings::new(intensity, target),
+                replacement,
+                read_policy,
+            });
+        }
+    }
+    None
+}
+
+#[allow(dead_code, clippy::too_many_arguments)]
+pub fn hybrid_parallel(
+    problem: &Problem,
+    ms: Vec<u32>,
+    bs: Vec<u32>,
+    n: u32,
+    mix: &Mix,
+    max_c: f64,
+    cost_function: CostKind,
+    intensity: ImitationIntensity,
+    target: ImitationTarget,
+    replacement: Replacement,
+    read_policy: ReadPolicy,
+    options: AgentOptions,
+    show_progress: bool,
+) -> Vec<Option<HybridResult>> {
+    sweep(iproduct!(ms, bs).collect(), n, show_progress, |(m, b)| {
+        hybrid(
+            problem,
+            m,
+            b,
+            mix,
+            max_c,
+            cost_function,
+            intensity,
+            target,
+            replacement,
+            read_policy,
+            options,
+        )
+    })
+}
+
+#[allow(dead_code, clippy::too_many_arguments)]
+pub fn hybrid_parallel_with<S: Strategy<Hybrid>>(
+    problem: &Problem,
+    ms: Vec<u32>,
+    bs: Vec<u32>,
+    n: u32,
+    mix: &Mix,
+    intensity: ImitationIntensity,
+    target: ImitationTarget,
+    replacement: Replacement,
+    read_policy: ReadPolicy,
+    max_c: f64,
+    show_progress: bool,
+    spawn: impl Fn() -> S + Sync,
+) -> Vec<Option<HybridResult>> {
+    sweep(iproduct!(ms, bs).collect(), n, show_progress, |(m, b)| {
+        hybrid_with(
+            problem,
+            m,
+            b,
+            mix,
+            intensity,
+            target,
+            replacement,
+            read_policy,
+            max_c,
+            &spawn,
+        )
+    })
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct HybridConfigData {
+    pub group_sizes: Option<Vec<u32>>,
+    pub blackboard_sizes: Option<Vec<u32>>,
+    pub group_range: Option<Range<u32>>,
+    pub blackboard_range: Option<Range<u32>>,
+    pub n_repeat: u32,
+    pub output: String,
+    pub use_threads: bool,
+    pub flush_frequency: u32,
+    pub mix: Mix,
+    #[serde(default)]
+    pub problem: ProblemConfigData,
+    #[serde(default)]
+    pub cost_function: CostKind,
+    #[serde(default)]
+    pub imitation_intensity: ImitationIntensity,
+    #[serde(default)]
+    pub imitation_target: ImitationTarget,
+    #[serde(default)]
+    pub replacement: Replacement,
+    #[serde(default)]
+    pub read_policy: ReadPolicy,
+    #[serde(default)]
+    pub agent: AgentOptions,
+}
+
+impl HybridConfigData {
+    // populates group_sizes and blackboard_sizes with Some
+    fn standardize(&mut self) {
+        let group_sizes = match &self.group_sizes {
+            Some(v) => v.clone(),
+            None => match &self.group_range {
+                Some(r) => (r.start..=r.end).collect(),
+                None => unreachable!(),
+            },
+        };
+        let blackboard_sizes = match &self.blackboard_sizes {
+            Some(v) => v.clone(),
+            None => match &self.blackboard_range {
+                Some(r) => (r.start..=r.end).collect(),
+                None => unreachable!(),
+            },
+        };
+        self.group_sizes = Some(group_sizes);
+        self.blackboard_sizes = Some(blackboard_sizes);
+    }
+}
+
+impl HybridConfigData {
+    pub fn log(&self) {
+        println!("info: starting batch",);
+        self.problem.log();
+        println!("    - output:          {}", self.output);
+        println!("    - use_threads:     {}", self.use_threads);
+        println!("    - n_repeat:        {}", self.n_repeat);
+        println!(
+            "    - group:           {:?}",
+            self.group_sizes.clone().unwrap()
+        );
+        println!(
+            "    - blackboard:      {:?}",
+            self.blackboard_sizes.clone().unwrap()
+        );
+        println!("    - mix:             {}", self.mix);
+        println!("    - flush_frequency: {}", self.flush_frequency);
+        println!("    - cost_function:   {}", self.cost_function);
+        println!("    - intensity:       {}", self.imitation_intensity);
+        println!("    - target:          {}", self.imitation_target);
+        println!("    - replacement:     {}", self.replacement);
+        println!("    - read_policy:     {}", self.read_policy);
+        self.agent.log();
+    }
+}
+
+pub fn hybrid_get_args() -> Result<HybridConfigData, Box<dyn std::error::Error>> {
+    let args: Vec<String> = env::args().collect();
+    let config = match args.len().cmp(&3) {
+        Ordering::Less => {
+            println!(
+                "info: no config file provided\ninfo: checking for config with name 'hybrid_default'"
+            );
+            Config::builder()
+                .add_source(config::File::with_name("hybrid_default"))
+                .set_default("flush_frequency", 100)?
+                .set_default("use_threads", true)?
+                .build()
+        }
+        Ordering::Greater => Err(config::ConfigError::Message(
+            "provided more arguments than allowed".to_string(),
+        )),
+        _ => {
+            let name = args.get(2).unwrap();
+            println!("info: checking for config with name '{}'", name);
+            Config::builder()
+                .add_source(config::File::with_name(name))
+                .set_default("flush_frequency", 100)?
+                .set_default("use_threads", true)?
+                .build()
+        }
+    }?;
+    println!("info: loaded config file");
+    let mut cfg = config.try_deserialize::<HybridConfigData>()?;
+    if cfg.n_repeat == 0 {
+        Err(config::ConfigError::Message(
+            "n_repeat must be > 0".to_string(),
+        ))?;
+    }
+    cfg.agent.validate().map_err(config::ConfigError::Message)?;
+    cfg.mix.validate().map_err(config::ConfigError::Message)?;
+    cfg.imitation_intensity
+        .validate()
+        .map_err(config::ConfigError::Message)?;
+    cfg.imitation_target
+        .validate()
+        .map_err(config::ConfigError::Message)?;
+    cfg.problem
+        .validate()
+        .map_err(config::ConfigError::Message)?;
+
+    cfg.standardize();
+    Ok(cfg)
+}
+
+pub struct HybridResultsWriter {
+    file: BufWriter<File>,
+    counter: u32,
+    flush_frequency: u32,
+}
+
+impl HybridResultsWriter {
+    pub fn new(filename: String, flush_frequency: u32) -> Self {
+        Self {
+            file: BufWriter::new(File::create(filename).unwrap()),
+            counter: 0,
+            flush_frequency,
+        }
+    }
+    pub fn write(&mut self, result: Option<HybridResult>) -> bool {
+        match result {
+            Some(r) => {
+                let _ = self.file.write(r.to_string().as_bytes());
+                if self.counter == self.flush_frequency {
+                    self.file.flush().unwrap();
+                    self.counter = 0;
+                }
+                self.counter += 1;
+                true
+            }
+            None => false,
+        }
+    }
+}
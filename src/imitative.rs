@@ -1,11 +1,49 @@
-use crate::common::{computational_cost, Agent, Problem};
+use crate::common::{computational_cost, random_agent, sweep, Problem, ProblemConfigData};
+use crate::cost::CostKind;
+use crate::network::Topology;
+use crate::options::{AgentOptions, ImitationIntensity, ImitationTarget};
+use crate::strategy::{Imitation, Strategy};
 use config::Config;
-use indicatif::ProgressBar;
 use itertools::{iproduct, Itertools};
-use rand::Rng;
-use rayon::prelude::*;
+use rand::{distributions::WeightedIndex, prelude::Distribution, seq::SliceRandom, Rng};
 use serde::Deserialize;
-use std::{cmp::Ordering, env, fs::File, io::prelude::*, io::BufWriter, ops::Range, sync::Mutex};
+use std::{cmp::Ordering, env, fmt, fs::File, io::prelude::*, io::BufWriter, ops::Range};
+
+// how the agents of an imitative or hybrid run imitate, reported with every result so rows of
+// different settings can be told apart. The cost function is the default for agents the caller
+// spawns, the models only know it if they build the agents themselves
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ImitationSettings {
+    pub cost_function: CostKind,
+    pub intensity: ImitationIntensity,
+    pub target: ImitationTarget,
+}
+
+impl ImitationSettings {
+    pub fn new(intensity: ImitationIntensity, target: ImitationTarget) -> Self {
+        ImitationSettings {
+            cost_function: CostKind::default(),
+            intensity,
+            target,
+        }
+    }
+
+    pub fn with_cost_function(mut self, cost_function: CostKind) -> Self {
+        self.cost_function = cost_function;
+        self
+    }
+}
+
+impl fmt::Display for ImitationSettings {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // intensity and target are quoted, their parameters are separated by commas
+        write!(
+            f,
+            "{},\"{}\",\"{}\"",
+            self.cost_function, self.intensity, self.target
+        )
+    }
+}
 
 #[derive(Debug)]
 pub struct ImitativeResult {
@@ -13,99 +51,208 @@ pub struct ImitativeResult {
     pub p: f64,
     pub t_star: f64,
     pub c: f64,
+    pub settings: ImitationSettings,
+    pub topology: String,
 }
 
-impl ToString for ImitativeResult {
-    fn to_string(&self) -> String {
-        format!("{},{},{},{}\n", self.m, self.p, self.t_star, self.c)
+impl fmt::Display for ImitativeResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "{},{},{},{},{},\"{}\"",
+            self.m, self.p, self.t_star, self.c, self.settings, self.topology
+        )
     }
 }
 
-pub fn imitative(m: u32, p: f64, max_c: f64) -> Option<ImitativeResult> {
-    let problem = Problem::new("DONALD", "GERALD", "ROBERT");
+#[allow(clippy::too_many_arguments)]
+pub fn imitative(
+    problem: &Problem,
+    m: u32,
+    p: f64,
+    max_c: f64,
+    cost_function: CostKind,
+    intensity: ImitationIntensity,
+    target: ImitationTarget,
+    topology: &Topology,
+    options: AgentOptions,
+) -> Option<ImitativeResult> {
+    imitative_with(problem, m, p, intensity, target, topology, max_c, || {
+        random_agent(problem, options, false).with_cost_function(cost_function)
+    })
+    .map(|r| ImitativeResult {
+        settings: r.settings.with_cost_function(cost_function),
+        ..r
+    })
+}
+
+// model of agent i under the target rule, costs holds the last reported cost of every agent
+// and neighbours the agents i can see, if it can not see all of them
+pub(crate) fn pick_model(
+    target: ImitationTarget,
+    i: usize,
+    best_agent: usize,
+    costs: &[u128],
+    neighbours: Option<&[usize]>,
+) -> Option<usize> {
+    let mut rng = rand::thread_rng();
+    let others: Vec<usize> = match neighbours {
+        Some(neighbours) => neighbours.to_vec(),
+        None => (0..costs.len()).filter(|j| *j != i).collect(),
+    };
+    match target {
+        ImitationTarget::Best if neighbours.is_none() => (i != best_agent).then_some(best_agent),
+        ImitationTarget::Best => others.into_iter().min_by_key(|j| costs[*j]),
+        ImitationTarget::RandomBetter => {
+            let better: Vec<usize> = others
+                .into_iter()
+                .filter(|j| costs[*j] < costs[i])
+                .collect();
+            better.choose(&mut rng).copied()
+        }
+        ImitationTarget::Tournament { k } => others
+            .choose_multiple(&mut rng, k)
+            .copied()
+            .min_by_key(|j| costs[*j]),
+        ImitationTarget::TopK { k } => {
+            let mut ranked = others;
+            ranked.sort_by_key(|j| costs[*j]);
+            ranked.truncate(k);
+            ranked.choose(&mut rng).copied()
+        }
+        ImitationTarget::Proportional => {
+            let weights = others.iter().map(|j| 1.0 / (1.0 + costs[*j] as f64));
+            let index = WeightedIndex::new(weights).ok()?;
+            Some(others[index.sample(&mut rng)])
+        }
+    }
+}
 
+// imitative model with the agents spawn creates
+#[allow(clippy::too_many_arguments)]
+pub fn imitative_with<S: Strategy<Imitation>>(
+    problem: &Problem,
+    m: u32,
+    p: f64,
+    intensity: ImitationIntensity,
+    target: ImitationTarget,
+    topology: &Topology,
+    max_c: f64,
+    spawn: impl Fn() -> S,
+) -> Option<ImitativeResult> {
     let delta = 1.0 / (m as f64);
-    let mut agents = Vec::new();
-    for _ in 0..m {
-        agents.push(Agent::new(&problem, false));
-    }
+    let mut imitation = Imitation::new(p, intensity);
+    let mut agents: Vec<S> = (0..m).map(|_| spawn()).collect();
     let mut best_agent: usize = 0;
-    let mut best_cost: u32 = u32::MAX;
+    let mut best_cost: u128 = u128::MAX;
+    let mut costs: Vec<u128> = vec![u128::MAX; agents.len()];
+    let neighbours = topology.neighbours(agents.len());
 
     let mut t = 1.0;
 
-    for a in agents.iter_mut() {
-        a.assign_random();
-        a.compute_cost();
-    }
-
-    for (i, a) in agents.iter().enumerate() {
-        if a.cost < best_cost {
+    for (i, a) in agents.iter_mut().enumerate() {
+        imitation.begin_turn();
+        a.contribute(&mut imitation);
+        costs[i] = imitation.reported_cost;
+        if imitation.reported_cost < best_cost {
             best_agent = i;
-            best_cost = a.cost;
+            best_cost = imitation.reported_cost;
         }
     }
 
-    while computational_cost(m, t) < max_c {
-        let best = agents.get(best_agent).unwrap().clone();
+    while computational_cost(problem, m, t) < max_c {
         let i: usize = rand::thread_rng().gen_range(0..agents.len());
+        imitation.model = pick_model(
+            target,
+            i,
+            best_agent,
+            &costs,
+            neighbours.as_ref().map(|n| n[i].as_slice()),
+        )
+        .map(|j| agents[j].assignment());
+        imitation.begin_turn();
         let a = agents.get_mut(i).unwrap();
 
-        if i != best_agent && rand::thread_rng().gen_bool(p) {
-            a.imitate(&best);
-        } else {
-            a.elementary_move();
-        }
+        a.observe(&imitation);
+        a.act();
         t += delta;
-        a.compute_cost();
-        if a.cost < best_cost {
+        a.contribute(&mut imitation);
+        costs[i] = imitation.reported_cost;
+        if imitation.reported_cost < best_cost {
             best_agent = i;
-            best_cost = a.cost;
+            best_cost = imitation.reported_cost;
         }
-        if a.cost == 0 {
+        if a.is_solved() {
             return Some(ImitativeResult {
                 m,
                 p,
                 t_star: t,
-                c: computational_cost(m, t),
+                c: computational_cost(problem, m, t),
+                settings: ImitationSettings::new(intensity, target),
+                topology: topology.to_string(),
             });
         }
     }
     None
 }
 
-#[allow(dead_code)]
+#[allow(dead_code, clippy::too_many_arguments)]
 pub fn imitative_parallel(
+    problem: &Problem,
     ms: Vec<u32>,
     ps: Vec<f64>,
     n: u32,
     max_c: f64,
+    cost_function: CostKind,
+    intensities: Vec<ImitationIntensity>,
+    target: ImitationTarget,
+    topology: &Topology,
+    options: AgentOptions,
     show_progress: bool,
 ) -> Vec<Option<ImitativeResult>> {
-    let results: Mutex<Vec<Option<ImitativeResult>>> = Mutex::new(vec![]);
-    let jobs_len = ms.len() * ps.len() * n as usize;
-
-    if show_progress {
-        let pb = Mutex::new(ProgressBar::new(jobs_len as u64));
-
-        iproduct!(ms, ps, 0..n)
-            .collect_vec()
-            .into_par_iter()
-            .for_each(|(m, p, _)| {
-                let r = imitative(m, p, max_c);
-                pb.lock().unwrap().inc(1);
-                results.lock().unwrap().push(r);
-            });
-    } else {
-        iproduct!(ms, ps, 0..n)
-            .collect_vec()
-            .into_par_iter()
-            .for_each(|(m, p, _)| {
-                let r = imitative(m, p, max_c);
-                results.lock().unwrap().push(r);
-            });
-    }
-    results.into_inner().expect("Error unwrapping results")
+    imitative_parallel_with(
+        problem,
+        ms,
+        ps,
+        n,
+        intensities,
+        target,
+        topology,
+        max_c,
+        show_progress,
+        || random_agent(problem, options, false).with_cost_function(cost_function),
+    )
+    .into_iter()
+    .map(|r| {
+        r.map(|r| ImitativeResult {
+            settings: r.settings.with_cost_function(cost_function),
+            ..r
+        })
+    })
+    .collect()
+}
+
+#[allow(dead_code, clippy::too_many_arguments)]
+pub fn imitative_parallel_with<S: Strategy<Imitation>>(
+    problem: &Problem,
+    ms: Vec<u32>,
+    ps: Vec<f64>,
+    n: u32,
+    intensities: Vec<ImitationIntensity>,
+    target: ImitationTarget,
+    topology: &Topology,
+    max_c: f64,
+    show_progress: bool,
+    spawn: impl Fn() -> S + Sync,
+) -> Vec<Option<ImitativeResult>> {
+    sweep(
+        iproduct!(ms, ps, intensities).collect(),
+        n,
+        show_progress,
+        |(m, p, intensity)| {
+            imitative_with(problem, m, p, intensity, target, topology, max_c, &spawn)
+        },
+    )
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -117,10 +264,24 @@ pub struct ImitativeConfigData {
     pub output: String,
     pub use_threads: bool,
     pub flush_frequency: u32,
+    #[serde(default)]
+    pub problem: ProblemConfigData,
+    #[serde(default)]
+    pub cost_function: CostKind,
+    #[serde(default)]
+    pub imitation_intensity: ImitationIntensity,
+    // swept instead of imitation_intensity if given
+    pub imitation_intensities: Option<Vec<ImitationIntensity>>,
+    #[serde(default)]
+    pub imitation_target: ImitationTarget,
+    #[serde(default)]
+    pub topology: Topology,
+    #[serde(default)]
+    pub agent: AgentOptions,
 }
 
 impl ImitativeConfigData {
-    // populates group_sizes and blackboard_sizes with Some
+    // populates group_sizes and imitation_intensities with Some
     fn standardize(&mut self) {
         let group_sizes = match &self.group_sizes {
             Some(v) => v.clone(),
@@ -130,12 +291,16 @@ impl ImitativeConfigData {
             },
         };
         self.group_sizes = Some(group_sizes);
+        if self.imitation_intensities.is_none() {
+            self.imitation_intensities = Some(vec![self.imitation_intensity]);
+        }
     }
 }
 
 impl ImitativeConfigData {
     pub fn log(&self) {
         println!("info: starting batch",);
+        self.problem.log();
         println!("    - output:          {}", self.output);
         println!("    - use_threads:     {}", self.use_threads);
         println!("    - n_repeat:        {}", self.n_repeat);
@@ -145,6 +310,19 @@ impl ImitativeConfigData {
         );
         println!("    - p:               {:?}", self.p_values.clone());
         println!("    - flush_frequency: {}", self.flush_frequency);
+        println!("    - cost_function:   {}", self.cost_function);
+        println!(
+            "    - intensity:       {}",
+            self.imitation_intensities
+                .as_ref()
+                .unwrap()
+                .iter()
+                .map(|i| i.to_string())
+                .join(", ")
+        );
+        println!("    - target:          {}", self.imitation_target);
+        println!("    - topology:        {}", self.topology);
+        self.agent.log();
     }
 }
 
@@ -181,8 +359,34 @@ pub fn imitative_get_args() -> Result<ImitativeConfigData, Box<dyn std::error::E
             "n_repeat must be > 0".to_string(),
         ))?;
     }
+    cfg.agent.validate().map_err(config::ConfigError::Message)?;
+    cfg.imitation_intensity
+        .validate()
+        .map_err(config::ConfigError::Message)?;
+    if let Some(intensities) = &cfg.imitation_intensities {
+        if intensities.is_empty() {
+            Err(config::ConfigError::Message(
+                "imitation_intensities must not be empty".to_string(),
+            ))?;
+        }
+        for intensity in intensities {
+            intensity.validate().map_err(config::ConfigError::Message)?;
+        }
+    }
+    cfg.imitation_target
+        .validate()
+        .map_err(config::ConfigError::Message)?;
+    cfg.topology.load().map_err(config::ConfigError::Message)?;
+    cfg.problem
+        .validate()
+        .map_err(config::ConfigError::Message)?;
 
     cfg.standardize();
+    for m in cfg.group_sizes.as_ref().unwrap() {
+        cfg.topology
+            .check_size(*m as usize)
+            .map_err(config::ConfigError::Message)?;
+    }
     Ok(cfg)
 }
 
@@ -215,3 +419,49 @@ impl ImitativeResultsWriter {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn targets_are_other_cheaper_agents() {
+        let costs = [5, 3, 8, 1, 4];
+        for _ in 0..100 {
+            for target in [
+                ImitationTarget::Best,
+                ImitationTarget::RandomBetter,
+                ImitationTarget::Tournament { k: 2 },
+                ImitationTarget::TopK { k: 2 },
+                ImitationTarget::Proportional,
+            ] {
+                for i in 0..costs.len() {
+                    assert_ne!(pick_model(target, i, 3, &costs, None), Some(i), "{target}");
+                }
+            }
+            // only better agents, and none for the best one
+            let better = pick_model(ImitationTarget::RandomBetter, 0, 3, &costs, None);
+            assert!(matches!(better, Some(1 | 3 | 4)));
+            assert_eq!(
+                pick_model(ImitationTarget::RandomBetter, 3, 3, &costs, None),
+                None
+            );
+            // the cheapest of all others and of the neighbours
+            let all = ImitationTarget::Tournament { k: 4 };
+            assert_eq!(pick_model(all, 0, 3, &costs, None), Some(3));
+            let top = ImitationTarget::TopK { k: 1 };
+            assert_eq!(pick_model(top, 3, 3, &costs, None), Some(1));
+            let neighbours = [1, 2];
+            let best = pick_model(ImitationTarget::Best, 0, 3, &costs, Some(&neighbours));
+            assert_eq!(best, Some(1));
+            let top = pick_model(
+                ImitationTarget::TopK { k: 2 },
+                0,
+                3,
+                &costs,
+                Some(&neighbours),
+            );
+            assert!(matches!(top, Some(1 | 2)));
+        }
+    }
+}
@@ -2,7 +2,37 @@
 
 mod blackboard;
 mod common;
+mod cost;
+mod generator;
+mod hybrid;
 mod imitative;
+mod network;
+mod number;
+mod options;
+mod solver;
+mod strategy;
 
-pub use blackboard::{blackboard, blackboard_parallel, BlackboardResult};
-pub use imitative::{imitative, imitative_parallel, ImitativeResult};
+pub use blackboard::{
+    blackboard, blackboard_parallel, blackboard_parallel_with, blackboard_with, BlackboardResult,
+    BlackboardResultsWriter,
+};
+pub use common::{
+    Agent, Hint, LeadingZeros, LetterAssignment, Operator, Problem, ProblemError, Solution,
+    UnknownLeadingZeros, LEADING_ZERO_PENALTY,
+};
+pub use cost::{CostKind, UnknownCostFunction};
+pub use generator::{generate, generate_with, validate_sizes, GeneratorError, GeneratorResult};
+pub use hybrid::{
+    hybrid, hybrid_parallel, hybrid_parallel_with, hybrid_with, HybridResult, HybridResultsWriter,
+};
+pub use imitative::{
+    imitative, imitative_parallel, imitative_parallel_with, imitative_with, ImitationSettings,
+    ImitativeResult, ImitativeResultsWriter,
+};
+pub use network::{Migration, Topology};
+pub use options::{
+    Acceptance, Adversaries, AdversaryMode, AgentOptions, ImitationIntensity, ImitationTarget, Mix,
+    MoveOperator, ReadPolicy, Replacement, UnknownAcceptance, UnknownAdversaryMode,
+    UnknownMoveOperator, UnknownReadPolicy, UnknownReplacement,
+};
+pub use strategy::{Blackboard, HintRecord, Hybrid, Imitation, Strategy};
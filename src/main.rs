@@ -1,8 +1,18 @@
 mod blackboard;
 mod common;
+mod cost;
+mod generator;
+mod hybrid;
 mod imitative;
+mod network;
+mod number;
+mod options;
+mod solver;
+mod strategy;
 
 use crate::blackboard::{blackboard_get_args, BlackboardResultsWriter};
+use crate::generator::{generator_get_args, GeneratorResultsWriter};
+use crate::hybrid::{hybrid_get_args, HybridResultsWriter};
 use crate::imitative::{imitative_get_args, ImitativeResultsWriter};
 use indicatif::ProgressBar;
 use itertools::{iproduct, Itertools};
@@ -12,6 +22,8 @@ use std::{cmp::Ordering, env, sync::Mutex};
 enum RunType {
     Blackboard,
     Imitative,
+    Hybrid,
+    Generate,
     Error,
 }
 
@@ -30,7 +42,7 @@ fn choose_type() -> RunType {
     match args.len().cmp(&2) {
         Ordering::Less => {
             println!(
-                "info: must provide either ('blackboard' | 'b') or ('imitative' | 'i') as run type"
+                "info: must provide either ('blackboard' | 'b'), ('imitative' | 'i'), ('hybrid' | 'h') or ('generate' | 'g') as run type"
             );
             RunType::Error
         }
@@ -45,6 +57,14 @@ fn choose_type() -> RunType {
                     println!("info: got run type imitative");
                     RunType::Imitative
                 }
+                "hybrid" | "h" => {
+                    println!("info: got run type hybrid");
+                    RunType::Hybrid
+                }
+                "generate" | "g" => {
+                    println!("info: got run type generate");
+                    RunType::Generate
+                }
                 _ => {
                     println!("info: invalid run type {}", t);
                     RunType::Error
@@ -60,39 +80,76 @@ fn run_blackboard() -> Result<(), Box<dyn std::error::Error>> {
     let config = blackboard_get_args()?;
     config.log();
 
+    let problem = config.problem.build()?;
     let group_sizes = config.group_sizes.unwrap();
     let blackboard_sizes = config.blackboard_sizes.unwrap();
+    let island_counts = config.island_counts.unwrap();
+    let migration = &config.migration;
+    let replacement = config.replacement;
+    let read_policy = config.read_policy;
+    let adversaries = config.adversaries;
     let compute_phi = config.compute_phi;
+    let options = config.agent;
 
-    let jobs_len = group_sizes.len() * blackboard_sizes.len() * config.n_repeat as usize;
+    let jobs_len =
+        group_sizes.len() * blackboard_sizes.len() * island_counts.len() * config.n_repeat as usize;
 
     if config.use_threads {
         let file = Mutex::new(BlackboardResultsWriter::new(
             config.output.clone(),
             config.flush_frequency,
-            compute_phi,
         ));
 
         let pb = Mutex::new(ProgressBar::new(jobs_len as u64));
 
-        iproduct!(group_sizes, blackboard_sizes, 0..config.n_repeat)
-            .collect_vec()
-            .into_par_iter()
-            .for_each(|(m, b, _)| {
-                let r = blackboard::blackboard(m, b, CUTOFF_COMP_COST, compute_phi);
-                pb.lock().unwrap().inc(1);
-                file.lock().unwrap().write(r);
-            });
+        iproduct!(
+            group_sizes,
+            blackboard_sizes,
+            island_counts,
+            0..config.n_repeat
+        )
+        .collect_vec()
+        .into_par_iter()
+        .for_each(|(m, b, g, _)| {
+            let r = blackboard::blackboard(
+                &problem,
+                m,
+                b,
+                g,
+                migration,
+                replacement,
+                read_policy,
+                adversaries,
+                CUTOFF_COMP_COST,
+                compute_phi,
+                options,
+            );
+            pb.lock().unwrap().inc(1);
+            file.lock().unwrap().write(r);
+        });
     } else {
-        let mut file = BlackboardResultsWriter::new(
-            config.output.clone(),
-            config.flush_frequency,
-            compute_phi,
-        );
+        let mut file = BlackboardResultsWriter::new(config.output.clone(), config.flush_frequency);
 
         let pb = ProgressBar::new(jobs_len as u64);
-        for (m, b, _) in iproduct!(group_sizes, blackboard_sizes, 0..config.n_repeat) {
-            let r = blackboard::blackboard(m, b, CUTOFF_COMP_COST, compute_phi);
+        for (m, b, g, _) in iproduct!(
+            group_sizes,
+            blackboard_sizes,
+            island_counts,
+            0..config.n_repeat
+        ) {
+            let r = blackboard::blackboard(
+                &problem,
+                m,
+                b,
+                g,
+                migration,
+                replacement,
+                read_policy,
+                adversaries,
+                CUTOFF_COMP_COST,
+                compute_phi,
+                options,
+            );
             pb.inc(1);
             file.write(r);
         }
@@ -104,10 +161,17 @@ fn run_imitative() -> Result<(), Box<dyn std::error::Error>> {
     let config = imitative_get_args()?;
     config.log();
 
+    let problem = config.problem.build()?;
     let group_sizes = config.group_sizes.unwrap();
     let p_values = config.p_values;
+    let cost_function = config.cost_function;
+    let intensities = config.imitation_intensities.unwrap();
+    let target = config.imitation_target;
+    let topology = &config.topology;
+    let options = config.agent;
 
-    let jobs_len = group_sizes.len() * p_values.len() * config.n_repeat as usize;
+    let jobs_len =
+        group_sizes.len() * p_values.len() * intensities.len() * config.n_repeat as usize;
 
     if config.use_threads {
         let file = Mutex::new(ImitativeResultsWriter::new(
@@ -117,11 +181,21 @@ fn run_imitative() -> Result<(), Box<dyn std::error::Error>> {
 
         let pb = Mutex::new(ProgressBar::new(jobs_len as u64));
 
-        iproduct!(group_sizes, p_values, 0..config.n_repeat)
+        iproduct!(group_sizes, p_values, intensities, 0..config.n_repeat)
             .collect_vec()
             .into_par_iter()
-            .for_each(|(m, p, _)| {
-                let r = imitative::imitative(m, p, CUTOFF_COMP_COST);
+            .for_each(|(m, p, intensity, _)| {
+                let r = imitative::imitative(
+                    &problem,
+                    m,
+                    p,
+                    CUTOFF_COMP_COST,
+                    cost_function,
+                    intensity,
+                    target,
+                    topology,
+                    options,
+                );
                 pb.lock().unwrap().inc(1);
                 file.lock().unwrap().write(r);
             });
@@ -129,8 +203,90 @@ fn run_imitative() -> Result<(), Box<dyn std::error::Error>> {
         let mut file = ImitativeResultsWriter::new(config.output.clone(), config.flush_frequency);
 
         let pb = ProgressBar::new(jobs_len as u64);
-        for (m, p, _) in iproduct!(group_sizes, p_values, 0..config.n_repeat) {
-            let r = imitative::imitative(m, p, CUTOFF_COMP_COST);
+        for (m, p, intensity, _) in
+            iproduct!(group_sizes, p_values, intensities, 0..config.n_repeat)
+        {
+            let r = imitative::imitative(
+                &problem,
+                m,
+                p,
+                CUTOFF_COMP_COST,
+                cost_function,
+                intensity,
+                target,
+                topology,
+                options,
+            );
+            pb.inc(1);
+            file.write(r);
+        }
+    }
+    Ok(())
+}
+
+fn run_hybrid() -> Result<(), Box<dyn std::error::Error>> {
+    let config = hybrid_get_args()?;
+    config.log();
+
+    let problem = config.problem.build()?;
+    let group_sizes = config.group_sizes.unwrap();
+    let blackboard_sizes = config.blackboard_sizes.unwrap();
+    let mix = &config.mix;
+    let cost_function = config.cost_function;
+    let intensity = config.imitation_intensity;
+    let target = config.imitation_target;
+    let replacement = config.replacement;
+    let read_policy = config.read_policy;
+    let options = config.agent;
+
+    let jobs_len = group_sizes.len() * blackboard_sizes.len() * config.n_repeat as usize;
+
+    if config.use_threads {
+        let file = Mutex::new(HybridResultsWriter::new(
+            config.output.clone(),
+            config.flush_frequency,
+        ));
+
+        let pb = Mutex::new(ProgressBar::new(jobs_len as u64));
+
+        iproduct!(group_sizes, blackboard_sizes, 0..config.n_repeat)
+            .collect_vec()
+            .into_par_iter()
+            .for_each(|(m, b, _)| {
+                let r = hybrid::hybrid(
+                    &problem,
+                    m,
+                    b,
+                    mix,
+                    CUTOFF_COMP_COST,
+                    cost_function,
+                    intensity,
+                    target,
+                    replacement,
+                    read_policy,
+                    options,
+                );
+                pb.lock().unwrap().inc(1);
+                file.lock().unwrap().write(r);
+            });
+    } else {
+        let mut file = HybridResultsWriter::new(config.output.clone(), config.flush_frequency);
+
+        let pb = ProgressBar::new(jobs_len as u64);
+        for (m, b, _) in iproduct!(group_sizes, blackboard_sizes, 0..config.n_repeat) {
+            let r = hybrid::hybrid(
+                &problem,
+                m,
+                b,
+                mix,
+                CUTOFF_COMP_COST,
+                cost_function,
+                intensity,
+                target,
+                replacement,
+                read_policy,
+                options,
+            );
             pb.inc(1);
             file.write(r);
         }
@@ -138,10 +294,65 @@ fn run_imitative() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    match choose_type() {
+fn run_generate() -> Result<(), Box<dyn std::error::Error>> {
+    let config = generator_get_args()?;
+    config.log();
+
+    let words = config.load_words()?;
+    let words = words.as_deref();
+    let jobs_len = config.n_problems as usize;
+
+    if config.use_threads {
+        let file = Mutex::new(GeneratorResultsWriter::new(
+            config.output.clone(),
+            config.flush_frequency,
+        ));
+
+        let pb = Mutex::new(ProgressBar::new(jobs_len as u64));
+
+        (0..config.n_problems).into_par_iter().for_each(|_| {
+            let r = generator::generate(
+                config.letters,
+                &config.word_lengths,
+                config.base,
+                words,
+                config.max_attempts,
+            );
+            // the sizes are validated when the config is loaded, so an error means that every
+            // attempt failed, which writes nothing
+            pb.lock().unwrap().inc(1);
+            file.lock().unwrap().write(r.ok());
+        });
+    } else {
+        let mut file = GeneratorResultsWriter::new(config.output.clone(), config.flush_frequency);
+
+        let pb = ProgressBar::new(jobs_len as u64);
+        for _ in 0..config.n_problems {
+            let r = generator::generate(
+                config.letters,
+                &config.word_lengths,
+                config.base,
+                words,
+                config.max_attempts,
+            );
+            pb.inc(1);
+            file.write(r.ok());
+        }
+    }
+    Ok(())
+}
+
+fn main() {
+    let result = match choose_type() {
         RunType::Blackboard => run_blackboard(),
         RunType::Imitative => run_imitative(),
-        RunType::Error => Err(InvalidRunType {})?,
+        RunType::Hybrid => run_hybrid(),
+        RunType::Generate => run_generate(),
+        RunType::Error => Err(InvalidRunType {}.into()),
+    };
+    // print the message rather than the debug representation of the error
+    if let Err(e) = result {
+        eprintln!("{}", e);
+        std::process::exit(1);
     }
 }
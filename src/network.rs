@@ -0,0 +1,319 @@
+use rand::{seq::SliceRandom, Rng};
+use serde::Deserialize;
+use std::{collections::BTreeSet, fmt, fs};
+
+// Social network the agents of the imitative model or the islands of the blackboard model live
+// on. An agent only takes its model from its neighbours and hints only migrate to neighbouring
+// islands, the fully connected network links every node to every other one.
+#[derive(Debug, Clone, PartialEq, Default, Deserialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum Topology {
+    #[default]
+    FullyConnected,
+    // every agent is linked to the k nearest agents on either side of a circle
+    Ring {
+        k: usize,
+    },
+    // agents fill the rows of a periodic grid width agents wide, linked to the four around them
+    Lattice {
+        width: usize,
+    },
+    // Watts-Strogatz, a ring whose links are rewired to a random agent with probability beta
+    SmallWorld {
+        k: usize,
+        beta: f64,
+    },
+    // Barabasi-Albert, every new agent links to `links` agents chosen in proportion to their degree
+    ScaleFree {
+        links: usize,
+    },
+    // one link per line of the file as two agent indices starting at 0, '#' starts a comment
+    EdgeList {
+        path: String,
+        #[serde(skip)]
+        edges: Vec<(usize, usize)>,
+    },
+}
+
+impl fmt::Display for Topology {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Topology::FullyConnected => write!(f, "fully_connected"),
+            Topology::Ring { k } => write!(f, "ring (k = {})", k),
+            Topology::Lattice { width } => write!(f, "lattice (width = {})", width),
+            Topology::SmallWorld { k, beta } => {
+                write!(f, "small_world (k = {}, beta = {})", k, beta)
+            }
+            Topology::ScaleFree { links } => write!(f, "scale_free (links = {})", links),
+            Topology::EdgeList { path, edges } => {
+                write!(f, "edge_list ({}, {} links)", path, edges.len())
+            }
+        }
+    }
+}
+
+impl Topology {
+    // checks the parameters and reads the links of an edge list
+    pub fn load(&mut self) -> Result<(), String> {
+        match self {
+            Topology::Ring { k: 0 } | Topology::SmallWorld { k: 0, .. } => {
+                Err("topology k must be > 0".to_string())
+            }
+            Topology::Lattice { width: 0 } => Err("topology width must be > 0".to_string()),
+            Topology::SmallWorld { beta, .. } if !(0.0..=1.0).contains(beta) => {
+                Err("topology beta must be between 0 and 1".to_string())
+            }
+            Topology::ScaleFree { links: 0 } => Err("topology links must be > 0".to_string()),
+            Topology::EdgeList { path, edges } => {
+                let text = fs::read_to_string(&*path)
+                    .map_err(|e| format!("could not read edge list '{}': {}", path, e))?;
+                *edges = parse_edges(&text)?;
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    // checks that the links of an edge list only name nodes of a network of m
+    pub fn check_size(&self, m: usize) -> Result<(), String> {
+        match self {
+            Topology::EdgeList { path, edges } => {
+                match edges.iter().find(|(a, b)| *a >= m || *b >= m) {
+                    Some((a, b)) => Err(format!(
+                        "edge list '{}' links {} and {}, but there are only {} nodes",
+                        path, a, b, m
+                    )),
+                    None => Ok(()),
+                }
+            }
+            _ => Ok(()),
+        }
+    }
+
+    // neighbours of each of the m agents, None if every agent sees every other one. Panics if an
+    // edge list does not pass check_size
+    pub fn neighbours(&self, m: usize) -> Option<Vec<Vec<usize>>> {
+        if let Err(e) = self.check_size(m) {
+            panic!("{}", e);
+        }
+        let mut graph = vec![BTreeSet::new(); m];
+        // the last row of a lattice may be short, links past it are left out
+        let mut link = |a: usize, b: usize| {
+            if a != b && a < m && b < m {
+                graph[a].insert(b);
+                graph[b].insert(a);
+            }
+        };
+        match self {
+            Topology::FullyConnected => return None,
+            Topology::Ring { k } => {
+                for i in 0..m {
+                    for d in 1..=*k {
+                        link(i, (i + d) % m);
+                    }
+                }
+            }
+            Topology::Lattice { width } => {
+                let height = m.div_ceil(*width);
+                for i in 0..m {
+                    let (x, y) = (i % width, i / width);
+                    link(i, y * width + (x + 1) % width);
+                    link(i, (y + 1) % height * width + x);
+                }
+            }
+            Topology::SmallWorld { k, beta } => {
+                for (a, b) in small_world(m, *k, *beta) {
+                    link(a, b);
+                }
+            }
+            Topology::ScaleFree { links } => {
+                for (a, b) in scale_free(m, *links) {
+                    link(a, b);
+                }
+            }
+            Topology::EdgeList { edges, .. } => {
+                for (a, b) in edges {
+                    link(*a, *b);
+                }
+            }
+        }
+        Some(graph.into_iter().map(|n| n.into_iter().collect()).collect())
+    }
+}
+
+// how hints of the blackboard model move between the boards of the islands
+#[derive(Debug, Clone, PartialEq, Default, Deserialize)]
+pub struct Migration {
+    // probability that the board of the agent that just acted sends one of its hints on
+    #[serde(default)]
+    pub rate: f64,
+    // boards a hint can migrate to, any other board if fully connected
+    #[serde(default)]
+    pub topology: Topology,
+}
+
+impl fmt::Display for Migration {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} along {}", self.rate, self.topology)
+    }
+}
+
+impl Migration {
+    pub fn load(&mut self) -> Result<(), String> {
+        if !(0.0..=1.0).contains(&self.rate) {
+            return Err("migration rate must be between 0 and 1".to_string());
+        }
+        self.topology.load()
+    }
+}
+
+fn parse_edges(text: &str) -> Result<Vec<(usize, usize)>, String> {
+    let mut edges = Vec::new();
+    for (n, line) in text.lines().enumerate() {
+        let line = line.split('#').next().unwrap().trim();
+        if line.is_empty() {
+            continue;
+        }
+        let ends: Vec<&str> = line
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|s| !s.is_empty())
+            .collect();
+        match ends[..] {
+            [a, b] => match (a.parse(), b.parse()) {
+                (Ok(a), Ok(b)) => edges.push((a, b)),
+                _ => return Err(format!("edge list line {} is not two agent indices", n + 1)),
+            },
+            _ => return Err(format!("edge list line {} is not two agent indices", n + 1)),
+        }
+    }
+    Ok(edges)
+}
+
+// ring of k links per side where the far end of every link moves to a random agent with
+// probability beta, unless that agent is already linked
+fn small_world(m: usize, k: usize, beta: f64) -> Vec<(usize, usize)> {
+    let mut rng = rand::thread_rng();
+    let mut edges: BTreeSet<(usize, usize)> = BTreeSet::new();
+    let key = |a: usize, b: usize| (a.min(b), a.max(b));
+    for i in 0..m {
+        for d in 1..=k {
+            edges.insert(key(i, (i + d) % m));
+        }
+    }
+    for i in 0..m {
+        for d in 1..=k {
+            let j = (i + d) % m;
+            if !rng.gen_bool(beta) || !edges.contains(&key(i, j)) {
+                continue;
+            }
+            let free: Vec<usize> = (0..m)
+                .filter(|x| *x != i && !edges.contains(&key(i, *x)))
+                .collect();
+            if let Some(x) = free.choose(&mut rng) {
+                edges.remove(&key(i, j));
+                edges.insert(key(i, *x));
+            }
+        }
+    }
+    edges.into_iter().collect()
+}
+
+// starts from links + 1 fully connected agents, every later agent links to `links` distinct
+// earlier ones, picked from a list holding each agent once per link it has
+fn scale_free(m: usize, links: usize) -> Vec<(usize, usize)> {
+    let mut rng = rand::thread_rng();
+    let seed = (links + 1).min(m);
+    let mut edges = Vec::new();
+    let mut ends = Vec::new();
+    for a in 0..seed {
+        for b in a + 1..seed {
+            edges.push((a, b));
+            ends.extend([a, b]);
+        }
+    }
+    for v in seed..m {
+        let mut targets = BTreeSet::new();
+        while targets.len() < links {
+            targets.insert(*ends.choose(&mut rng).unwrap());
+        }
+        for t in targets {
+            edges.push((v, t));
+            ends.extend([v, t]);
+        }
+    }
+    edges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn degrees(neighbours: &[Vec<usize>]) -> Vec<usize> {
+        neighbours.iter().map(|n| n.len()).collect()
+    }
+
+    fn edge_count(neighbours: &[Vec<usize>]) -> usize {
+        neighbours.iter().map(|n| n.len()).sum::<usize>() / 2
+    }
+
+    #[test]
+    fn fully_connected_has_no_neighbour_lists() {
+        assert_eq!(Topology::FullyConnected.neighbours(5), None);
+    }
+
+    #[test]
+    fn ring_degree() {
+        for k in 1..4 {
+            let neighbours = Topology::Ring { k }.neighbours(10).unwrap();
+            assert_eq!(degrees(&neighbours), vec![2 * k; 10]);
+            assert_eq!(neighbours[0][..k], (1..=k).collect::<Vec<usize>>());
+        }
+    }
+
+    #[test]
+    fn lattice_neighbours() {
+        let neighbours = Topology::Lattice { width: 3 }.neighbours(9).unwrap();
+        assert_eq!(neighbours[4], vec![1, 3, 5, 7]);
+        // the grid is periodic
+        assert_eq!(neighbours[0], vec![1, 2, 3, 6]);
+        // a short last row leaves out the links past it
+        let neighbours = Topology::Lattice { width: 2 }.neighbours(5).unwrap();
+        assert_eq!(neighbours[4], vec![0, 2]);
+    }
+
+    #[test]
+    fn small_world_keeps_the_edge_count() {
+        for beta in [0.0, 0.5, 1.0] {
+            let neighbours = Topology::SmallWorld { k: 2, beta }.neighbours(20).unwrap();
+            assert_eq!(edge_count(&neighbours), 40);
+        }
+    }
+
+    #[test]
+    fn scale_free_edge_count() {
+        let links = 3;
+        let neighbours = Topology::ScaleFree { links }.neighbours(20).unwrap();
+        // a complete graph of links + 1 agents, then links for every later agent
+        assert_eq!(edge_count(&neighbours), 6 + (20 - 4) * links);
+        assert!(degrees(&neighbours).iter().all(|d| *d >= links));
+    }
+
+    #[test]
+    fn edge_list() {
+        let edges = parse_edges("# a triangle\n0 1\n1, 2\n\n2 0 # closed\n").unwrap();
+        assert_eq!(edges, vec![(0, 1), (1, 2), (2, 0)]);
+        assert!(parse_edges("0 1\n2\n").is_err());
+        assert!(parse_edges("0 x\n").is_err());
+
+        let topology = Topology::EdgeList {
+            path: "edges.txt".to_string(),
+            edges,
+        };
+        assert!(topology.check_size(3).is_ok());
+        assert_eq!(
+            topology.check_size(2).unwrap_err(),
+            "edge list 'edges.txt' links 1 and 2, but there are only 2 nodes"
+        );
+        assert_eq!(topology.neighbours(4).unwrap()[3], Vec::<usize>::new());
+    }
+}
@@ -0,0 +1,199 @@
+use std::cmp::Ordering;
+
+// Arbitrary precision natural number kept as digits in the base of a problem, least significant
+// digit first. Only used once the value of a word no longer fits into a u128, so it favours
+// simplicity over speed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Natural {
+    base: u32,
+    digits: Vec<u32>,
+}
+
+impl Natural {
+    pub(crate) fn from_digits(base: u32, digits: impl IntoIterator<Item = u32>) -> Self {
+        let mut n = Natural {
+            base,
+            digits: digits.into_iter().collect(),
+        };
+        n.trim();
+        n
+    }
+
+    pub(crate) fn one(base: u32) -> Self {
+        Natural::from_digits(base, [1])
+    }
+
+    pub(crate) fn from_u128(base: u32, mut value: u128) -> Self {
+        let mut digits = Vec::new();
+        while value > 0 {
+            digits.push((value % base as u128) as u32);
+            value /= base as u128;
+        }
+        Natural::from_digits(base, digits)
+    }
+
+    fn trim(&mut self) {
+        while self.digits.last() == Some(&0) {
+            self.digits.pop();
+        }
+    }
+
+    pub(crate) fn add(&self, other: &Natural) -> Natural {
+        let base = self.base as u64;
+        let len = self.digits.len().max(other.digits.len());
+        let mut digits = Vec::with_capacity(len + 1);
+        let mut carry = 0;
+        for i in 0..len {
+            let total = carry
+                + self.digits.get(i).copied().unwrap_or(0) as u64
+                + other.digits.get(i).copied().unwrap_or(0) as u64;
+            digits.push((total % base) as u32);
+            carry = total / base;
+        }
+        digits.push(carry as u32);
+        Natural::from_digits(self.base, digits)
+    }
+
+    pub(crate) fn mul(&self, other: &Natural) -> Natural {
+        let base = self.base as u64;
+        let mut digits = vec![0u32; self.digits.len() + other.digits.len()];
+        for (i, a) in self.digits.iter().enumerate() {
+            let mut carry = 0;
+            for (j, b) in other.digits.iter().enumerate() {
+                let total = digits[i + j] as u64 + *a as u64 * *b as u64 + carry;
+                digits[i + j] = (total % base) as u32;
+                carry = total / base;
+            }
+            let mut k = i + other.digits.len();
+            while carry > 0 {
+                let total = digits[k] as u64 + carry;
+                digits[k] = (total % base) as u32;
+                carry = total / base;
+                k += 1;
+            }
+        }
+        Natural::from_digits(self.base, digits)
+    }
+
+    // number of digits without leading zeros
+    pub(crate) fn len(&self) -> usize {
+        self.digits.len()
+    }
+
+    // digit i places from the right
+    pub(crate) fn digit(&self, i: usize) -> u32 {
+        self.digits.get(i).copied().unwrap_or(0)
+    }
+
+    // value of the lowest n digits
+    pub(crate) fn truncated(&self, n: usize) -> Natural {
+        Natural::from_digits(self.base, self.digits.iter().take(n).copied())
+    }
+
+    pub(crate) fn abs_diff(&self, other: &Natural) -> Natural {
+        let (large, small) = match self.cmp(other) {
+            Ordering::Less => (other, self),
+            _ => (self, other),
+        };
+        let base = self.base as i64;
+        let mut digits = Vec::with_capacity(large.digits.len());
+        let mut borrow = 0;
+        for (i, d) in large.digits.iter().enumerate() {
+            let mut total = *d as i64 - small.digits.get(i).copied().unwrap_or(0) as i64 - borrow;
+            borrow = 0;
+            if total < 0 {
+                total += base;
+                borrow = 1;
+            }
+            digits.push(total as u32);
+        }
+        Natural::from_digits(self.base, digits)
+    }
+
+    pub(crate) fn to_u128(&self) -> Option<u128> {
+        self.digits.iter().rev().try_fold(0u128, |v, d| {
+            v.checked_mul(self.base as u128)?.checked_add(*d as u128)
+        })
+    }
+}
+
+impl PartialOrd for Natural {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Natural {
+    // both numbers are in the same base and carry no leading zeros
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.digits
+            .len()
+            .cmp(&other.digits.len())
+            .then_with(|| self.digits.iter().rev().cmp(other.digits.iter().rev()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    #[test]
+    fn add_carries() {
+        let nines = Natural::from_u128(10, 999);
+        let sum = nines.add(&Natural::one(10));
+        assert_eq!(sum, Natural::from_digits(10, [0, 0, 0, 1]));
+        assert_eq!(sum.len(), 4);
+        let a = Natural::from_digits(7, [6, 6]);
+        assert_eq!(a.add(&a).to_u128(), Some(96));
+    }
+
+    #[test]
+    fn mul_carries() {
+        let a = Natural::from_u128(10, 999);
+        assert_eq!(a.mul(&a).to_u128(), Some(998_001));
+        assert_eq!(a.mul(&Natural::from_digits(10, [])).len(), 0);
+        let b = Natural::from_u128(16, 0xffff);
+        assert_eq!(b.mul(&b).to_u128(), Some(0xffff * 0xffff));
+    }
+
+    #[test]
+    fn abs_diff_ignores_order() {
+        let a = Natural::from_u128(10, 1000);
+        let b = Natural::from_u128(10, 1);
+        assert_eq!(a.abs_diff(&b).to_u128(), Some(999));
+        assert_eq!(b.abs_diff(&a).to_u128(), Some(999));
+        assert_eq!(a.abs_diff(&a).len(), 0);
+        assert!(b < a);
+    }
+
+    #[test]
+    fn to_u128_overflow() {
+        let max = Natural::from_u128(10, u128::MAX);
+        assert_eq!(max.to_u128(), Some(u128::MAX));
+        assert_eq!(max.add(&Natural::one(10)).to_u128(), None);
+        let max = Natural::from_u128(2, u128::MAX);
+        assert_eq!(max.len(), 128);
+        assert_eq!(max.add(&Natural::one(2)).to_u128(), None);
+    }
+
+    #[test]
+    fn matches_u128() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..1000 {
+            let base = rng.gen_range(2..=40);
+            let x = rng.gen::<u64>() as u128;
+            let y = rng.gen::<u64>() as u128;
+            let (a, b) = (Natural::from_u128(base, x), Natural::from_u128(base, y));
+            assert_eq!(a.add(&b).to_u128(), Some(x + y));
+            assert_eq!(a.mul(&b).to_u128(), Some(x * y));
+            assert_eq!(a.abs_diff(&b).to_u128(), Some(x.abs_diff(y)));
+            assert_eq!(a.cmp(&b), x.cmp(&y));
+            let n = rng.gen_range(0..10);
+            let modulus = (base as u128).pow(n as u32);
+            assert_eq!(a.truncated(n).to_u128(), Some(x % modulus));
+            assert_eq!(a.digit(n) as u128, x / modulus % base as u128);
+        }
+    }
+}
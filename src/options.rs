@@ -0,0 +1,645 @@
+use serde::Deserialize;
+use std::{fmt, str::FromStr};
+
+// how an agent changes its assignment when it does not take a hint or imitate
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MoveOperator {
+    // one random letter moves to a random digit, trading places with the letter holding it
+    #[default]
+    Reassign,
+    // two random letters trade digits
+    Swap,
+    // rotation_size random letters pass their digits on in a cycle
+    Rotate,
+    // every letter of one random column gets a new digit out of their own and the free ones
+    ColumnScramble,
+    // with restart_probability the whole assignment is drawn again, otherwise a reassign
+    Restart,
+}
+
+impl fmt::Display for MoveOperator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MoveOperator::Reassign => write!(f, "reassign"),
+            MoveOperator::Swap => write!(f, "swap"),
+            MoveOperator::Rotate => write!(f, "rotate"),
+            MoveOperator::ColumnScramble => write!(f, "column_scramble"),
+            MoveOperator::Restart => write!(f, "restart"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownMoveOperator(String);
+
+impl std::error::Error for UnknownMoveOperator {}
+impl fmt::Display for UnknownMoveOperator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "error: unknown move operator '{}', expected reassign, swap, rotate, column_scramble or restart",
+            self.0
+        )
+    }
+}
+
+impl FromStr for MoveOperator {
+    type Err = UnknownMoveOperator;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "reassign" => Ok(MoveOperator::Reassign),
+            "swap" => Ok(MoveOperator::Swap),
+            "rotate" => Ok(MoveOperator::Rotate),
+            "column_scramble" => Ok(MoveOperator::ColumnScramble),
+            "restart" => Ok(MoveOperator::Restart),
+            _ => Err(UnknownMoveOperator(s.to_string())),
+        }
+    }
+}
+
+// whether an agent keeps the assignment an elementary move gave it, judged by its cost
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Acceptance {
+    // every move is kept, the agent is a random walker
+    #[default]
+    Always,
+    // only moves that do not raise the cost are kept
+    Greedy,
+    // a move raising the cost by d is kept with probability exp(-d / temperature)
+    Metropolis,
+    // metropolis with temperature * cooling_rate^k after k moves
+    ExponentialAnnealing,
+    // metropolis with temperature / ln(k + 2) after k moves
+    LogarithmicAnnealing,
+}
+
+impl fmt::Display for Acceptance {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Acceptance::Always => write!(f, "always"),
+            Acceptance::Greedy => write!(f, "greedy"),
+            Acceptance::Metropolis => write!(f, "metropolis"),
+            Acceptance::ExponentialAnnealing => write!(f, "exponential_annealing"),
+            Acceptance::LogarithmicAnnealing => write!(f, "logarithmic_annealing"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownAcceptance(String);
+
+impl std::error::Error for UnknownAcceptance {}
+impl fmt::Display for UnknownAcceptance {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "error: unknown acceptance '{}', expected always, greedy, metropolis, exponential_annealing or logarithmic_annealing",
+            self.0
+        )
+    }
+}
+
+impl FromStr for Acceptance {
+    type Err = UnknownAcceptance;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "always" => Ok(Acceptance::Always),
+            "greedy" => Ok(Acceptance::Greedy),
+            "metropolis" => Ok(Acceptance::Metropolis),
+            "exponential_annealing" => Ok(Acceptance::ExponentialAnnealing),
+            "logarithmic_annealing" => Ok(Acceptance::LogarithmicAnnealing),
+            _ => Err(UnknownAcceptance(s.to_string())),
+        }
+    }
+}
+
+// which hint leaves a full blackboard to make room for a new one
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Replacement {
+    // a uniformly random hint
+    #[default]
+    Random,
+    // the hint posted first
+    Fifo,
+    // the hint that has not been read for the longest time, posting counts as a read
+    LeastRecentlyRead,
+    // the hint the fewest agents of the board hold
+    LeastPopular,
+    // a random hint with probability proportional to 1 / (1 + quality)
+    QualityWeighted,
+}
+
+impl fmt::Display for Replacement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Replacement::Random => write!(f, "random"),
+            Replacement::Fifo => write!(f, "fifo"),
+            Replacement::LeastRecentlyRead => write!(f, "least_recently_read"),
+            Replacement::LeastPopular => write!(f, "least_popular"),
+            Replacement::QualityWeighted => write!(f, "quality_weighted"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownReplacement(String);
+
+impl std::error::Error for UnknownReplacement {}
+impl fmt::Display for UnknownReplacement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "error: unknown replacement '{}', expected random, fifo, least_recently_read, least_popular or quality_weighted",
+            self.0
+        )
+    }
+}
+
+impl FromStr for Replacement {
+    type Err = UnknownReplacement;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "random" => Ok(Replacement::Random),
+            "fifo" => Ok(Replacement::Fifo),
+            "least_recently_read" => Ok(Replacement::LeastRecentlyRead),
+            "least_popular" => Ok(Replacement::LeastPopular),
+            "quality_weighted" => Ok(Replacement::QualityWeighted),
+            _ => Err(UnknownReplacement(s.to_string())),
+        }
+    }
+}
+
+// how an agent picks the hint it reads from the blackboard
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReadPolicy {
+    // every hint is equally likely
+    #[default]
+    Uniform,
+    // in proportion to (1 + up votes) / (2 + up votes + down votes)
+    Votes,
+    // in proportion to 1 + the number of times it was read
+    Reads,
+    // in proportion to 1 + the number of agents of the board holding it
+    Holders,
+}
+
+impl fmt::Display for ReadPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ReadPolicy::Uniform => write!(f, "uniform"),
+            ReadPolicy::Votes => write!(f, "votes"),
+            ReadPolicy::Reads => write!(f, "reads"),
+            ReadPolicy::Holders => write!(f, "holders"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownReadPolicy(String);
+
+impl std::error::Error for UnknownReadPolicy {}
+impl fmt::Display for UnknownReadPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "error: unknown read policy '{}', expected uniform, votes, reads or holders",
+            self.0
+        )
+    }
+}
+
+impl FromStr for ReadPolicy {
+    type Err = UnknownReadPolicy;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "uniform" => Ok(ReadPolicy::Uniform),
+            "votes" => Ok(ReadPolicy::Votes),
+            "reads" => Ok(ReadPolicy::Reads),
+            "holders" => Ok(ReadPolicy::Holders),
+            _ => Err(UnknownReadPolicy(s.to_string())),
+        }
+    }
+}
+
+// what the adversaries of the blackboard model post instead of an honest hint
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AdversaryMode {
+    // the letters of a random column with digits that satisfy it but are not part of any solution
+    #[default]
+    False,
+    // the letters of a random column with random digits
+    Random,
+    // an honest hint, one letter of which gets another digit with probability e
+    Noisy,
+}
+
+impl fmt::Display for AdversaryMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AdversaryMode::False => write!(f, "false"),
+            AdversaryMode::Random => write!(f, "random"),
+            AdversaryMode::Noisy => write!(f, "noisy"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownAdversaryMode(String);
+
+impl std::error::Error for UnknownAdversaryMode {}
+impl fmt::Display for UnknownAdversaryMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "error: unknown adversary mode '{}', expected false, random or noisy",
+            self.0
+        )
+    }
+}
+
+impl FromStr for AdversaryMode {
+    type Err = UnknownAdversaryMode;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "false" => Ok(AdversaryMode::False),
+            "random" => Ok(AdversaryMode::Random),
+            "noisy" => Ok(AdversaryMode::Noisy),
+            _ => Err(UnknownAdversaryMode(s.to_string())),
+        }
+    }
+}
+
+// agents of the blackboard model that post misinformation, the first round(fraction * m) of them
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize)]
+pub struct Adversaries {
+    #[serde(default)]
+    pub fraction: f64,
+    #[serde(default)]
+    pub mode: AdversaryMode,
+    // probability that a noisy agent corrupts the hint it posts
+    #[serde(default)]
+    pub e: f64,
+}
+
+impl fmt::Display for Adversaries {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.mode {
+            AdversaryMode::Noisy => write!(f, "{} {} (e = {})", self.fraction, self.mode, self.e),
+            _ => write!(f, "{} {}", self.fraction, self.mode),
+        }
+    }
+}
+
+impl Adversaries {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(0.0..=1.0).contains(&self.fraction) {
+            return Err("adversaries fraction must be between 0 and 1".to_string());
+        }
+        if !(0.0..=1.0).contains(&self.e) {
+            return Err("adversaries e must be between 0 and 1".to_string());
+        }
+        Ok(())
+    }
+
+    // number of adversaries among m agents
+    pub fn count(&self, m: u32) -> u32 {
+        (self.fraction * m as f64).round() as u32
+    }
+}
+
+// what an agent copies from the best agent when it imitates
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum ImitationIntensity {
+    // k random letters
+    Letters { k: usize },
+    // every letter of one random column
+    Column,
+    // every letter with probability q, possibly none
+    Independent { q: f64 },
+}
+
+impl Default for ImitationIntensity {
+    fn default() -> Self {
+        ImitationIntensity::Letters { k: 1 }
+    }
+}
+
+impl fmt::Display for ImitationIntensity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ImitationIntensity::Letters { k } => write!(f, "letters (k = {})", k),
+            ImitationIntensity::Column => write!(f, "column"),
+            ImitationIntensity::Independent { q } => write!(f, "independent (q = {})", q),
+        }
+    }
+}
+
+impl ImitationIntensity {
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            ImitationIntensity::Letters { k } if *k == 0 => {
+                Err("imitation_intensity k must be > 0".to_string())
+            }
+            ImitationIntensity::Independent { q } if *q <= 0.0 || *q > 1.0 => {
+                Err("imitation_intensity q must be > 0 and at most 1".to_string())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+// which agent an imitating agent takes as its model, never itself
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum ImitationTarget {
+    // the agent with the lowest cost found so far
+    #[default]
+    Best,
+    // a uniformly random agent with a lower cost, no imitation if there is none
+    RandomBetter,
+    // the cheapest of k random other agents
+    Tournament {
+        k: usize,
+    },
+    // a uniformly random other agent among the k cheapest
+    TopK {
+        k: usize,
+    },
+    // a random other agent with probability proportional to 1 / (1 + cost)
+    Proportional,
+}
+
+impl fmt::Display for ImitationTarget {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ImitationTarget::Best => write!(f, "best"),
+            ImitationTarget::RandomBetter => write!(f, "random_better"),
+            ImitationTarget::Tournament { k } => write!(f, "tournament (k = {})", k),
+            ImitationTarget::TopK { k } => write!(f, "top_k (k = {})", k),
+            ImitationTarget::Proportional => write!(f, "proportional"),
+        }
+    }
+}
+
+impl ImitationTarget {
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            ImitationTarget::Tournament { k } | ImitationTarget::TopK { k } if *k == 0 => {
+                Err("imitation_target k must be > 0".to_string())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+// how the agents of the hybrid model use the blackboard and the model they imitate, as (p, q)
+// for every agent: p is the probability of imitating the model and q of reading the board
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum Mix {
+    // shares of the agents that only use the blackboard, only imitate with probability p or
+    // use neither, the counts are rounded and the loners take what is left
+    Types {
+        blackboard: f64,
+        imitator: f64,
+        loner: f64,
+        p: f64,
+    },
+    // every agent reads the board with probability q, and if that gives it nothing to follow
+    // imitates with probability p
+    Channels {
+        p: f64,
+        q: f64,
+    },
+}
+
+impl fmt::Display for Mix {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Mix::Types {
+                blackboard,
+                imitator,
+                loner,
+                p,
+            } => write!(
+                f,
+                "types (blackboard = {}, imitator = {}, loner = {}, p = {})",
+                blackboard, imitator, loner, p
+            ),
+            Mix::Channels { p, q } => write!(f, "channels (p = {}, q = {})", p, q),
+        }
+    }
+}
+
+impl Mix {
+    pub fn validate(&self) -> Result<(), String> {
+        let probability = |x: f64| (0.0..=1.0).contains(&x);
+        match *self {
+            Mix::Types {
+                blackboard,
+                imitator,
+                loner,
+                p,
+            } => {
+                if blackboard < 0.0 || imitator < 0.0 || loner < 0.0 {
+                    Err("mix shares must be >= 0".to_string())
+                } else if blackboard + imitator + loner <= 0.0 {
+                    Err("mix shares must not all be 0".to_string())
+                } else if !probability(p) {
+                    Err("mix p must be between 0 and 1".to_string())
+                } else {
+                    Ok(())
+                }
+            }
+            Mix::Channels { p, q } if !probability(p) || !probability(q) => {
+                Err("mix p and q must be between 0 and 1".to_string())
+            }
+            Mix::Channels { .. } => Ok(()),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Mix::Types { .. } => "types",
+            Mix::Channels { .. } => "channels",
+        }
+    }
+
+    // (p, q) the results report, blackboard users of the types mode always read the board
+    pub fn probabilities(&self) -> (f64, f64) {
+        match *self {
+            Mix::Types { p, .. } => (p, 1.0),
+            Mix::Channels { p, q } => (p, q),
+        }
+    }
+
+    // agents among m that read the board, that imitate and that do neither. The types mode counts
+    // the type each agent was given, whatever its probabilities, the channels mode counts every
+    // agent for each channel it uses
+    pub fn counts(&self, m: usize) -> (usize, usize, usize) {
+        match *self {
+            Mix::Types {
+                blackboard,
+                imitator,
+                loner,
+                ..
+            } => {
+                let total = blackboard + imitator + loner;
+                let users = (m as f64 * blackboard / total).round() as usize;
+                let imitators = ((m as f64 * imitator / total).round() as usize).min(m - users);
+                (users, imitators, m - users - imitators)
+            }
+            Mix::Channels { p, q } => {
+                let uses = |x: f64| if x > 0.0 { m } else { 0 };
+                (uses(q), uses(p), m - uses(p.max(q)))
+            }
+        }
+    }
+
+    // (p, q) of each of the m agents, blackboard users first, then imitators, then loners
+    pub fn roles(&self, m: usize) -> Vec<(f64, f64)> {
+        match *self {
+            Mix::Types { p, .. } => {
+                let (users, imitators, _) = self.counts(m);
+                let mut roles = vec![(0.0, 1.0); users];
+                roles.resize(users + imitators, (p, 0.0));
+                roles.resize(m, (0.0, 0.0));
+                roles
+            }
+            Mix::Channels { p, q } => vec![(p, q); m],
+        }
+    }
+}
+
+// agent section of the config files, shared by all models
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub struct AgentOptions {
+    #[serde(default)]
+    pub move_operator: MoveOperator,
+    #[serde(default = "default_rotation_size")]
+    pub rotation_size: usize,
+    #[serde(default = "default_restart_probability")]
+    pub restart_probability: f64,
+    #[serde(default)]
+    pub acceptance: Acceptance,
+    #[serde(default = "default_temperature")]
+    pub temperature: f64,
+    #[serde(default = "default_cooling_rate")]
+    pub cooling_rate: f64,
+}
+
+fn default_rotation_size() -> usize {
+    3
+}
+
+fn default_restart_probability() -> f64 {
+    0.01
+}
+
+fn default_temperature() -> f64 {
+    1.0
+}
+
+fn default_cooling_rate() -> f64 {
+    0.999
+}
+
+impl Default for AgentOptions {
+    fn default() -> Self {
+        AgentOptions {
+            move_operator: MoveOperator::default(),
+            rotation_size: default_rotation_size(),
+            restart_probability: default_restart_probability(),
+            acceptance: Acceptance::default(),
+            temperature: default_temperature(),
+            cooling_rate: default_cooling_rate(),
+        }
+    }
+}
+
+impl AgentOptions {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.rotation_size < 2 {
+            return Err("rotation_size must be >= 2".to_string());
+        }
+        if !(0.0..=1.0).contains(&self.restart_probability) {
+            return Err("restart_probability must be between 0 and 1".to_string());
+        }
+        if self.temperature <= 0.0 {
+            return Err("temperature must be > 0".to_string());
+        }
+        if self.cooling_rate <= 0.0 || self.cooling_rate >= 1.0 {
+            return Err("cooling_rate must be between 0 and 1".to_string());
+        }
+        Ok(())
+    }
+
+    // temperature of the metropolis rule after an agent made the given number of moves
+    pub fn temperature(&self, moves: u32) -> f64 {
+        match self.acceptance {
+            Acceptance::ExponentialAnnealing => {
+                self.temperature * self.cooling_rate.powf(moves as f64)
+            }
+            Acceptance::LogarithmicAnnealing => self.temperature / (moves as f64 + 2.0).ln(),
+            _ => self.temperature,
+        }
+    }
+
+    pub fn log(&self) {
+        println!("    - move_operator:   {}", self.move_operator);
+        match self.move_operator {
+            MoveOperator::Rotate => println!("    - rotation_size:   {}", self.rotation_size),
+            MoveOperator::Restart => {
+                println!("    - restart:         {}", self.restart_probability)
+            }
+            _ => {}
+        }
+        println!("    - acceptance:      {}", self.acceptance);
+        match self.acceptance {
+            Acceptance::Always | Acceptance::Greedy => {}
+            Acceptance::ExponentialAnnealing => {
+                println!("    - temperature:     {}", self.temperature);
+                println!("    - cooling_rate:    {}", self.cooling_rate);
+            }
+            _ => println!("    - temperature:     {}", self.temperature),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mix_counts_types() {
+        let mix = Mix::Types {
+            blackboard: 0.5,
+            imitator: 0.25,
+            loner: 0.25,
+            p: 0.0,
+        };
+        // an imitator that never imitates is still counted as one
+        assert_eq!(mix.counts(8), (4, 2, 2));
+        let roles = mix.roles(8);
+        assert_eq!(roles.iter().filter(|r| **r == (0.0, 1.0)).count(), 4);
+        assert_eq!(roles.iter().filter(|r| **r == (0.0, 0.0)).count(), 4);
+    }
+
+    #[test]
+    fn mix_counts_channels() {
+        assert_eq!(Mix::Channels { p: 0.5, q: 0.5 }.counts(6), (6, 6, 0));
+        assert_eq!(Mix::Channels { p: 0.0, q: 0.5 }.counts(6), (6, 0, 0));
+        assert_eq!(Mix::Channels { p: 0.0, q: 0.0 }.counts(6), (0, 0, 6));
+    }
+}
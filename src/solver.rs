@@ -0,0 +1,195 @@
+use crate::common::{Operator, Problem, Solution};
+use crate::number::Natural;
+
+// Exact backtracking solver that works through the columns from the least significant digit.
+// Operand letters of a column are enumerated, the result letter is then forced by the column,
+// so most branches are cut off before every letter has a digit. Subtraction is solved as the
+// equivalent addition, for multiplication the lowest i + 1 digits of the product are forced by
+// the lowest i + 1 digits of the operands.
+pub(crate) struct Solver {
+    letters: Vec<char>,
+    base: u32,
+    multiply: bool,
+    // letter indices of every word, least significant digit first
+    operands: Vec<Vec<usize>>,
+    result: Vec<usize>,
+    width: usize,
+    // letters starting a word, which may not be zero unless leading zeros are allowed
+    leading: Vec<bool>,
+    digits: Vec<Option<u32>>,
+    used: Vec<bool>,
+    solutions: Vec<Solution>,
+}
+
+impl Solver {
+    pub(crate) fn new(
+        operands: &[&str],
+        operator: Operator,
+        result: &str,
+        base: u32,
+        allow_leading_zeros: bool,
+    ) -> Self {
+        // a - b - c = r is solved as r + b + c = a
+        let (operands, result): (Vec<&str>, &str) = match operator {
+            Operator::Subtract => (
+                [result]
+                    .into_iter()
+                    .chain(operands.iter().skip(1).copied())
+                    .collect(),
+                operands[0],
+            ),
+            _ => (operands.to_vec(), result),
+        };
+
+        let mut letters: Vec<char> = operands
+            .iter()
+            .chain([&result])
+            .flat_map(|w| w.chars())
+            .collect();
+        letters.sort_unstable();
+        letters.dedup();
+        let index = |w: &str| -> Vec<usize> {
+            w.chars()
+                .rev()
+                .map(|c| letters.iter().position(|l| *l == c).unwrap())
+                .collect()
+        };
+        let operands: Vec<Vec<usize>> = operands.iter().map(|w| index(w)).collect();
+        let result = index(result);
+
+        let width = operands
+            .iter()
+            .chain([&result])
+            .map(|w| w.len())
+            .max()
+            .unwrap_or(0);
+
+        let mut leading = vec![false; letters.len()];
+        for w in operands.iter().chain([&result]) {
+            if let Some(l) = w.last().filter(|_| !allow_leading_zeros) {
+                leading[*l] = true;
+            }
+        }
+
+        Solver {
+            digits: vec![None; letters.len()],
+            used: vec![false; base as usize],
+            base,
+            multiply: operator == Operator::Multiply,
+            letters,
+            operands,
+            result,
+            width,
+            leading,
+            solutions: Vec::new(),
+        }
+    }
+
+    pub(crate) fn solve(mut self) -> Vec<Solution> {
+        self.column(0, 0);
+        self.solutions
+    }
+
+    fn allowed(&self, letter: usize, digit: u32) -> bool {
+        !self.used[digit as usize] && (digit != 0 || !self.leading[letter])
+    }
+
+    fn assign(&mut self, letter: usize, digit: u32) {
+        self.digits[letter] = Some(digit);
+        self.used[digit as usize] = true;
+    }
+
+    fn unassign(&mut self, letter: usize) {
+        if let Some(digit) = self.digits[letter].take() {
+            self.used[digit as usize] = false;
+        }
+    }
+
+    fn digit(&self, word: &[usize], col: usize) -> u32 {
+        word.get(col).map_or(0, |l| self.digits[*l].unwrap())
+    }
+
+    // value of the lowest n digits of a word
+    fn truncated(&self, word: &[usize], n: usize) -> Natural {
+        Natural::from_digits(self.base, (0..n).map(|col| self.digit(word, col)))
+    }
+
+    // digit of the result forced by column col, and the carry into the next column
+    fn forced(&self, col: usize, carry: u32) -> (u32, u32) {
+        if self.multiply {
+            let operands = self.operands.iter().map(|w| self.truncated(w, col + 1));
+            let product = Problem::truncated_product(self.base, col + 1, operands);
+            (product.digit(col), 0)
+        } else {
+            let sum: u32 = self
+                .operands
+                .iter()
+                .map(|w| self.digit(w, col))
+                .sum::<u32>()
+                + carry;
+            (sum % self.base, sum / self.base)
+        }
+    }
+
+    // after the last column nothing may be carried out, or the full product has to match
+    fn complete(&self, carry: u32) -> bool {
+        if self.multiply {
+            let product = self.operands.iter().fold(Natural::one(self.base), |p, w| {
+                p.mul(&self.truncated(w, w.len()))
+            });
+            product == self.truncated(&self.result, self.result.len())
+        } else {
+            carry == 0
+        }
+    }
+
+    fn column(&mut self, col: usize, carry: u32) {
+        if col == self.width {
+            if self.complete(carry) {
+                let solution = self
+                    .letters
+                    .iter()
+                    .copied()
+                    .zip(self.digits.iter().map(|d| d.unwrap()))
+                    .collect();
+                self.solutions.push(solution);
+            }
+            return;
+        }
+
+        // enumerate the operand letters of this column that have no digit yet
+        let free = self
+            .operands
+            .iter()
+            .filter_map(|w| w.get(col))
+            .copied()
+            .find(|l| self.digits[*l].is_none());
+        if let Some(letter) = free {
+            for digit in 0..self.base {
+                if self.allowed(letter, digit) {
+                    self.assign(letter, digit);
+                    self.column(col, carry);
+                    self.unassign(letter);
+                }
+            }
+            return;
+        }
+
+        let (digit, carry) = self.forced(col, carry);
+        match self.result.get(col).copied() {
+            Some(r) => match self.digits[r] {
+                Some(d) if d == digit => self.column(col + 1, carry),
+                Some(_) => {}
+                None if self.allowed(r, digit) => {
+                    self.assign(r, digit);
+                    self.column(col + 1, carry);
+                    self.unassign(r);
+                }
+                None => {}
+            },
+            // an operand is longer than the result, the missing digit is a zero
+            None if digit == 0 => self.column(col + 1, carry),
+            None => {}
+        }
+    }
+}
@@ -0,0 +1,450 @@
+use crate::common::{Hint, Problem, Solution};
+use crate::options::{ImitationIntensity, ReadPolicy, Replacement};
+use rand::seq::SliceRandom;
+use std::{
+    cell::Cell,
+    collections::{hash_map::DefaultHasher, HashMap},
+    hash::{Hash, Hasher},
+};
+
+// Behaviour of an agent in the simulation loops. In every step the loop lets one agent look at
+// the shared environment, change its own assignment and then share what it found. The model
+// decides what the environment is, Agent implements the behaviour of both models and anything
+// else that implements Strategy can be run through the same drivers.
+pub trait Strategy<E> {
+    // looks at the environment and remembers what it wants to use
+    fn observe(&mut self, environment: &E);
+    // changes its own assignment
+    fn act(&mut self);
+    // shares what it found with the environment. In an Imitation, and the imitation of a Hybrid,
+    // this is where the agent reports its cost in reported_cost, the driver resets it to u128::MAX
+    // before every turn, so an agent that reports nothing is never anybody's model
+    fn contribute(&mut self, environment: &mut E);
+    fn is_solved(&self) -> bool;
+    // current digit of every letter
+    fn assignment(&self) -> Solution;
+    // share of the observed hints that were correct, NaN if it is not tracked
+    fn hint_accuracy(&self) -> f64 {
+        f64::NAN
+    }
+}
+
+// what the blackboard knows about one of its hints
+#[derive(Debug, Clone, Default)]
+pub struct HintRecord {
+    // agent that posted the hint, None if nobody's turn was announced
+    pub poster: Option<usize>,
+    // clock ticks of the post and the last read, posting counts as a read
+    pub posted: u64,
+    pub last_read: Cell<u64>,
+    pub reads: Cell<u32>,
+    // readers that followed the hint and lowered or raised their cost by it
+    pub up_votes: u32,
+    pub down_votes: u32,
+}
+
+// hints the agents of the blackboard model share, at most size of them. Every post and read ticks
+// a clock, so the board knows when each hint was posted and last read. It also counts the posted
+// hints that are not part of any solution and those an adversary forged
+#[derive(Debug, Clone)]
+pub struct Blackboard {
+    hints: Vec<Hint>,
+    records: Vec<HintRecord>,
+    // number of agents holding each hint by its hash, whether it is on the board or not. Only
+    // counted if a policy of the board reads it
+    holders: HashMap<u64, usize>,
+    clock: Cell<u64>,
+    // agent whose turn it is, the poster of what is posted
+    turn: Option<usize>,
+    // hints that reached the board and are not part of any solution, received ones included
+    bad_posts: u32,
+    forged_posts: u32,
+    size: usize,
+    replacement: Replacement,
+    read_policy: ReadPolicy,
+}
+
+impl Blackboard {
+    pub fn new(size: usize, replacement: Replacement, read_policy: ReadPolicy) -> Self {
+        Blackboard {
+            hints: Vec::with_capacity(size),
+            records: Vec::with_capacity(size),
+            holders: HashMap::new(),
+            clock: Cell::new(0),
+            turn: None,
+            bad_posts: 0,
+            forged_posts: 0,
+            size,
+            replacement,
+            read_policy,
+        }
+    }
+
+    pub fn hints(&self) -> &[Hint] {
+        &self.hints
+    }
+
+    // records of the hints, in the same order
+    pub fn records(&self) -> &[HintRecord] {
+        &self.records
+    }
+
+    pub fn bad_posts(&self) -> u32 {
+        self.bad_posts
+    }
+
+    pub fn forged_posts(&self) -> u32 {
+        self.forged_posts
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.hints.len() >= self.size
+    }
+
+    pub fn contains(&self, hint: &Hint) -> bool {
+        self.hints.contains(hint)
+    }
+
+    // whether the read policy weighs hints by their votes, readers only judge hints if it does
+    pub fn reads_votes(&self) -> bool {
+        self.read_policy == ReadPolicy::Votes
+    }
+
+    fn counts_holders(&self) -> bool {
+        self.read_policy == ReadPolicy::Holders || self.replacement == Replacement::LeastPopular
+    }
+
+    fn key(hint: &Hint) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        hint.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    fn tick(&self) -> u64 {
+        self.clock.set(self.clock.get() + 1);
+        self.clock.get()
+    }
+
+    pub fn begin_turn(&mut self, agent: usize) {
+        self.turn = Some(agent);
+    }
+
+    // weight the read policy gives the hint at index
+    fn weight(&self, index: usize) -> f64 {
+        let record = &self.records[index];
+        match self.read_policy {
+            ReadPolicy::Uniform => 1.0,
+            ReadPolicy::Votes => {
+                (1 + record.up_votes) as f64 / (2 + record.up_votes + record.down_votes) as f64
+            }
+            ReadPolicy::Reads => 1.0 + record.reads.get() as f64,
+            ReadPolicy::Holders => 1.0 + self.holders(&self.hints[index]) as f64,
+        }
+    }
+
+    // index of the hint the read policy picks, None if the board is empty
+    pub fn sample(&self) -> Option<usize> {
+        let mut rng = rand::thread_rng();
+        let indices: Vec<usize> = (0..self.hints.len()).collect();
+        match self.read_policy {
+            ReadPolicy::Uniform => indices.choose(&mut rng).copied(),
+            _ => indices
+                .choose_weighted(&mut rng, |i| self.weight(*i))
+                .ok()
+                .copied(),
+        }
+    }
+
+    // the hint at index, remembering that it was read
+    pub fn read(&self, index: usize) -> &Hint {
+        let record = &self.records[index];
+        record.last_read.set(self.tick());
+        record.reads.set(record.reads.get() + 1);
+        &self.hints[index]
+    }
+
+    // a reader judges a hint of the board, ignored if the hint has left it
+    pub fn vote(&mut self, hint: &Hint, up: bool) {
+        if let Some(i) = self.hints.iter().position(|h| h == hint) {
+            if up {
+                self.records[i].up_votes += 1;
+            } else {
+                self.records[i].down_votes += 1;
+            }
+        }
+    }
+
+    // an agent found these hints
+    pub fn hold(&mut self, hints: &[Hint]) {
+        if !self.counts_holders() {
+            return;
+        }
+        for hint in hints {
+            *self.holders.entry(Blackboard::key(hint)).or_insert(0) += 1;
+        }
+    }
+
+    // an agent no longer holds these hints
+    pub fn release(&mut self, hints: &[Hint]) {
+        if !self.counts_holders() {
+            return;
+        }
+        for hint in hints {
+            let key = Blackboard::key(hint);
+            if let Some(n) = self.holders.get_mut(&key) {
+                *n -= 1;
+                if *n == 0 {
+                    self.holders.remove(&key);
+                }
+            }
+        }
+    }
+
+    // 0 for every hint unless a policy of the board reads the holders
+    pub fn holders(&self, hint: &Hint) -> usize {
+        self.holders
+            .get(&Blackboard::key(hint))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    // a full board ignores the hint, forged marks a hint an adversary made up or corrupted
+    pub fn post(&mut self, hint: Hint, forged: bool, problem: &Problem) {
+        if self.is_full() {
+            return;
+        }
+        self.bad_posts += u32::from(!hint.is_correct(problem));
+        self.forged_posts += u32::from(forged);
+        self.insert(hint, self.turn);
+    }
+
+    fn insert(&mut self, hint: Hint, poster: Option<usize>) {
+        if !self.is_full() {
+            let now = self.tick();
+            self.hints.push(hint);
+            self.records.push(HintRecord {
+                poster,
+                posted: now,
+                last_read: Cell::new(now),
+                ..HintRecord::default()
+            });
+        }
+    }
+
+    pub fn remove(&mut self, index: usize) -> Hint {
+        self.records.remove(index);
+        self.hints.remove(index)
+    }
+
+    // index of the hint the replacement policy drops out of the candidate indices
+    pub fn victim(&self, problem: &Problem, candidates: &[usize]) -> Option<usize> {
+        let mut rng = rand::thread_rng();
+        // ties are broken at random
+        let mut candidates = candidates.to_vec();
+        candidates.shuffle(&mut rng);
+        match self.replacement {
+            Replacement::Random => candidates.first().copied(),
+            Replacement::Fifo => candidates
+                .into_iter()
+                .min_by_key(|i| self.records[*i].posted),
+            Replacement::LeastRecentlyRead => candidates
+                .into_iter()
+                .min_by_key(|i| self.records[*i].last_read.get()),
+            Replacement::LeastPopular => candidates
+                .into_iter()
+                .min_by_key(|i| self.holders(&self.hints[*i])),
+            Replacement::QualityWeighted => candidates
+                .choose_weighted(&mut rng, |i| {
+                    1.0 / (1.0 + self.hints[*i].quality(problem) as f64)
+                })
+                .ok()
+                .copied(),
+        }
+    }
+
+    // takes a hint from another board, keeping its poster. A full board drops the hint its
+    // replacement policy picks to make room. Returns false if the hint was already known
+    pub fn receive(&mut self, hint: Hint, poster: Option<usize>, problem: &Problem) -> bool {
+        if self.contains(&hint) || self.size == 0 {
+            return false;
+        }
+        if self.is_full() {
+            let all: Vec<usize> = (0..self.hints.len()).collect();
+            if let Some(i) = self.victim(problem, &all) {
+                self.remove(i);
+            }
+        }
+        self.bad_posts += u32::from(!hint.is_correct(problem));
+        self.insert(hint, poster);
+        true
+    }
+}
+
+// what an agent of the imitative model sees, and the cost it reports back
+#[derive(Debug, Clone)]
+pub struct Imitation {
+    // probability of imitating the model instead of making an elementary move
+    pub p: f64,
+    // how much of the model an imitation copies
+    pub intensity: ImitationIntensity,
+    // assignment of the best agent, None while the best agent itself acts
+    pub model: Option<Solution>,
+    // cost the agent whose turn it is reported by contributing, u128::MAX until it does
+    pub reported_cost: u128,
+}
+
+impl Imitation {
+    pub fn new(p: f64, intensity: ImitationIntensity) -> Self {
+        Imitation {
+            p,
+            intensity,
+            model: None,
+            reported_cost: u128::MAX,
+        }
+    }
+
+    pub fn begin_turn(&mut self) {
+        self.reported_cost = u128::MAX;
+    }
+}
+
+// both channels of the hybrid model, set up for the agent whose turn it is
+#[derive(Debug, Clone)]
+pub struct Hybrid {
+    pub blackboard: Blackboard,
+    // p of the imitation is the probability that the agent imitates the model
+    pub imitation: Imitation,
+    // probability that the agent reads the board, an agent that never does shares nothing on it
+    pub q: f64,
+}
+
+impl Hybrid {
+    pub fn new(
+        size: usize,
+        replacement: Replacement,
+        read_policy: ReadPolicy,
+        intensity: ImitationIntensity,
+    ) -> Self {
+        Hybrid {
+            blackboard: Blackboard::new(size, replacement, read_policy),
+            imitation: Imitation::new(0.0, intensity),
+            q: 0.0,
+        }
+    }
+
+    pub fn begin_turn(&mut self, agent: usize, (p, q): (f64, f64)) {
+        self.blackboard.begin_turn(agent);
+        self.imitation.begin_turn();
+        self.imitation.p = p;
+        self.q = q;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::{random_agent, LetterAssignment};
+    use crate::options::AgentOptions;
+
+    // rounds of every agent taking a turn on the board, as the blackboard model runs them
+    fn run(board: &mut Blackboard, problem: &Problem, rounds: usize) {
+        let mut agents: Vec<_> = (0..5)
+            .map(|_| random_agent(problem, AgentOptions::default(), false))
+            .collect();
+        for _ in 0..rounds {
+            for (i, a) in agents.iter_mut().enumerate() {
+                a.observe(&*board);
+                <_ as Strategy<Blackboard>>::act(a);
+                board.begin_turn(i);
+                a.contribute(board);
+            }
+        }
+    }
+
+    fn votes(board: &Blackboard) -> u32 {
+        board
+            .records()
+            .iter()
+            .map(|r| r.up_votes + r.down_votes)
+            .sum()
+    }
+
+    #[test]
+    fn default_policies_skip_votes_and_holders() {
+        let problem = Problem::parse("SEND + MORE = MONEY", 10).unwrap();
+        let mut board = Blackboard::new(3, Replacement::default(), ReadPolicy::default());
+        run(&mut board, &problem, 200);
+        assert!(!board.hints().is_empty());
+        assert_eq!(votes(&board), 0);
+        assert!(board.holders.is_empty());
+
+        let mut board = Blackboard::new(3, Replacement::LeastPopular, ReadPolicy::Votes);
+        run(&mut board, &problem, 200);
+        assert!(votes(&board) > 0);
+        assert!(!board.holders.is_empty());
+    }
+
+    #[test]
+    fn turns_reset_the_reported_cost() {
+        let intensity = ImitationIntensity::default();
+        let mut hybrid = Hybrid::new(1, Replacement::default(), ReadPolicy::default(), intensity);
+        hybrid.imitation.reported_cost = 3;
+        hybrid.begin_turn(0, (0.5, 0.5));
+        assert_eq!(hybrid.imitation.reported_cost, u128::MAX);
+    }
+
+    // a board of size 3 holding the hints a, b and c, posted in that order
+    fn full_board(problem: &Problem, replacement: Replacement) -> (Blackboard, Vec<Hint>) {
+        let mut board = Blackboard::new(3, replacement, ReadPolicy::default());
+        let hints: Vec<Hint> = "abcd"
+            .chars()
+            .map(|letter| Hint::new([LetterAssignment { letter, value: 1 }]))
+            .collect();
+        for hint in &hints[..3] {
+            board.post(hint.clone(), false, problem);
+        }
+        (board, hints)
+    }
+
+    #[test]
+    fn replacement_policies_evict_their_victim() {
+        let problem = Problem::parse("A + B = C", 10).unwrap();
+        // the letter of the hint that makes room for d on a full board
+        let evicted = |board: &mut Blackboard, hints: &[Hint]| {
+            assert!(board.receive(hints[3].clone(), None, &problem));
+            let left: Vec<&Hint> = hints.iter().filter(|h| !board.contains(h)).collect();
+            assert_eq!(left.len(), 1);
+            left[0].assignments()[0].letter
+        };
+
+        let (mut board, hints) = full_board(&problem, Replacement::Fifo);
+        board.read(0);
+        assert_eq!(evicted(&mut board, &hints), 'a');
+
+        let (mut board, hints) = full_board(&problem, Replacement::LeastRecentlyRead);
+        board.read(0);
+        assert_eq!(evicted(&mut board, &hints), 'b');
+
+        let (mut board, hints) = full_board(&problem, Replacement::LeastPopular);
+        board.read(2);
+        board.hold(&hints[..2]);
+        assert_eq!(evicted(&mut board, &hints), 'c');
+    }
+
+    #[test]
+    fn votes_weigh_reads() {
+        let problem = Problem::parse("A + B = C", 10).unwrap();
+        let (mut board, hints) = full_board(&problem, Replacement::default());
+        board.vote(&hints[0], true);
+        board.vote(&hints[2], false);
+        // the read policy of the board is uniform, votes are ignored
+        assert_eq!(board.weight(0), board.weight(2));
+
+        board.read_policy = ReadPolicy::Votes;
+        assert!(board.weight(0) > board.weight(1));
+        assert!(board.weight(1) > board.weight(2));
+        assert_eq!(board.weight(1), 0.5);
+        let reads = (0..1000).filter(|_| board.sample() == Some(0)).count();
+        assert!(reads > 1000 / 3, "{reads}");
+    }
+}